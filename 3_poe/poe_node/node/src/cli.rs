@@ -38,4 +38,7 @@ pub enum Subcommand {
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Verify that a document was notarized by the poe pallet.
+	VerifyClaim(crate::verify_claim::VerifyClaimCmd),
 }
@@ -0,0 +1,169 @@
+//! The `verify-claim` subcommand: look up the proof of existence of a local document.
+
+use std::{fs, path::{Path, PathBuf}};
+
+use codec::{Decode, Encode};
+use node_template_runtime::{pallet_poe::ClaimInfo, AccountId, BlockNumber};
+use sc_cli::{Error, Result};
+use sp_core::{blake2_128, blake2_256, crypto::Ss58Codec, twox_128};
+use structopt::StructOpt;
+
+/// Name of the poe pallet in `construct_runtime!`, used as the storage prefix.
+const POE_PALLET: &[u8] = b"PoeModule";
+/// Name of the storage map holding the claims.
+const PROOFS_STORAGE: &[u8] = b"Proofs";
+
+/// Verify that a document was notarized by the poe pallet.
+///
+/// The claim is the blake2-256 hash of the file, as computed by the front-end.
+#[derive(Debug, StructOpt)]
+pub struct VerifyClaimCmd {
+	/// Path of the document to verify.
+	#[structopt(parse(from_os_str))]
+	pub file: PathBuf,
+
+	/// HTTP RPC endpoint of the node to query.
+	#[structopt(long, default_value = "http://127.0.0.1:9933")]
+	pub url: String,
+
+	/// Read the claim from a state snapshot produced by `export-state` instead of
+	/// querying a node.
+	#[structopt(long, parse(from_os_str))]
+	pub snapshot: Option<PathBuf>,
+}
+
+impl VerifyClaimCmd {
+	/// Run the command.
+	pub fn run(&self) -> Result<()> {
+		let document = fs::read(&self.file)?;
+		let claim = blake2_256(&document).to_vec();
+		let key = format!("0x{}", hex::encode(proofs_key(&claim)));
+
+		let value = match &self.snapshot {
+			Some(path) => read_snapshot(path, &key)?,
+			None => query_node(&self.url, &key)?,
+		};
+
+		println!("Claim: 0x{}", hex::encode(&claim));
+
+		match value {
+			Some(value) => {
+				let bytes = decode_hex(&value)?;
+				let info = ClaimInfo::<AccountId, BlockNumber>::decode(&mut &bytes[..])
+					.map_err(|e| Error::Input(format!("Invalid claim value: {}", e)))?;
+				println!("Owner: {}", info.owner.to_ss58check());
+				println!("Block: {}", info.created_at);
+				if info.updated_at != info.created_at {
+					println!("Last updated: {}", info.updated_at);
+				}
+				if !info.metadata.is_empty() {
+					println!("Metadata: 0x{}", hex::encode(&info.metadata));
+				}
+			},
+			None => println!("No claim exists for this document."),
+		}
+
+		Ok(())
+	}
+}
+
+// Storage key of `Proofs[claim]`, a `Blake2_128Concat` map
+fn proofs_key(claim: &[u8]) -> Vec<u8> {
+	let encoded = claim.encode();
+
+	let mut key = twox_128(POE_PALLET).to_vec();
+	key.extend_from_slice(&twox_128(PROOFS_STORAGE));
+	key.extend_from_slice(&blake2_128(&encoded));
+	key.extend_from_slice(&encoded);
+	key
+}
+
+// Fetch the storage value at `key` on the best block of the node
+fn query_node(url: &str, key: &str) -> Result<Option<String>> {
+	let response: serde_json::Value = ureq::post(url)
+		.send_json(serde_json::json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": "state_getStorage",
+			"params": [key],
+		}))
+		.map_err(|e| Error::Input(format!("Request to {} failed: {}", url, e)))?
+		.into_json()?;
+
+	if let Some(error) = response.get("error") {
+		return Err(Error::Input(format!("Node returned an error: {}", error)));
+	}
+
+	Ok(response["result"].as_str().map(String::from))
+}
+
+// Look up `key` in the raw genesis storage of an exported chain spec
+fn read_snapshot(path: &Path, key: &str) -> Result<Option<String>> {
+	snapshot_value(&fs::read(path)?, key)
+}
+
+// Look up `key` in the `genesis.raw.top` storage of a JSON chain spec
+fn snapshot_value(snapshot: &[u8], key: &str) -> Result<Option<String>> {
+	let snapshot: serde_json::Value = serde_json::from_slice(snapshot)
+		.map_err(|e| Error::Input(format!("Invalid snapshot: {}", e)))?;
+
+	let top = snapshot["genesis"]["raw"]["top"].as_object().ok_or_else(|| {
+		Error::Input("Snapshot has no raw storage, export it with `export-state`.".into())
+	})?;
+
+	Ok(top.get(key).and_then(|v| v.as_str()).map(String::from))
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+	hex::decode(value.trim_start_matches("0x"))
+		.map_err(|e| Error::Input(format!("Invalid hex value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn proofs_key_matches_the_runtime_storage_key() {
+		// twox128("PoeModule") ++ twox128("Proofs") ++ blake2_128(claim) ++ claim, with the
+		// claim SCALE encoded
+		let expected = concat!(
+			"9eb97c526df32e16270d7f7e80249998",
+			"91de4e018111ab7179083f8ba1d213b6",
+			"5cc1e862d47b89e16d589b01dd23348f",
+			"0c010203",
+		);
+		assert_eq!(hex::encode(proofs_key(&[1, 2, 3])), expected);
+	}
+
+	#[test]
+	fn snapshot_value_reads_the_raw_genesis_storage() {
+		let info = ClaimInfo::<AccountId, BlockNumber> {
+			owner: AccountId::from([7; 32]),
+			created_at: 3,
+			updated_at: 5,
+			metadata: vec![1, 2],
+		};
+		let key = format!("0x{}", hex::encode(proofs_key(&[1, 2, 3])));
+		let value = format!("0x{}", hex::encode(info.encode()));
+		let snapshot = serde_json::json!({
+			"name": "Development",
+			"genesis": { "raw": { "top": { (key.clone()): value.clone() }, "childrenDefault": {} } },
+		});
+		let snapshot = serde_json::to_vec(&snapshot).unwrap();
+
+		let found = snapshot_value(&snapshot, &key).unwrap();
+		assert_eq!(found, Some(value));
+		let bytes = decode_hex(&found.unwrap()).unwrap();
+		assert_eq!(ClaimInfo::<AccountId, BlockNumber>::decode(&mut &bytes[..]).unwrap(), info);
+
+		assert_eq!(snapshot_value(&snapshot, "0x00").unwrap(), None);
+	}
+
+	#[test]
+	fn snapshot_value_rejects_specs_without_raw_storage() {
+		let snapshot = serde_json::json!({ "genesis": { "runtime": {} } });
+		assert!(snapshot_value(&serde_json::to_vec(&snapshot).unwrap(), "0x00").is_err());
+		assert!(snapshot_value(b"not json", "0x00").is_err());
+	}
+}
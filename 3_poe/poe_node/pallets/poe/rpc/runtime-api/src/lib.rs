@@ -0,0 +1,18 @@
+//! Runtime API definition for the poe pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi<AccountId, BlockNumber, Hash> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// Check a leaf and its Merkle proof against a notarized root and return the owner
+		/// and block number of the Merkle claim if the proof is valid.
+		fn verify_merkle_claim(root: Hash, leaf: Hash, proof: Vec<Hash>) -> Option<(AccountId, BlockNumber)>;
+	}
+}
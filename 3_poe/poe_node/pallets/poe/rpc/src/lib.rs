@@ -0,0 +1,73 @@
+//! RPC interface for the poe pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_poe_runtime_api::PoeApi as PoeRuntimeApi;
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+#[rpc]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber, Hash> {
+	/// Check a leaf and its Merkle proof against a notarized root. Returns the owner and block
+	/// number of the Merkle claim, or `null` if the root is unknown or the proof is invalid.
+	#[rpc(name = "poe_verifyMerkleClaim")]
+	fn verify_merkle_claim(
+		&self,
+		root: Hash,
+		leaf: Hash,
+		proof: Vec<Hash>,
+		at: Option<BlockHash>,
+	) -> Result<Option<(AccountId, BlockNumber)>>;
+}
+
+/// An implementation of poe specific RPC methods.
+pub struct Poe<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Poe<C, B> {
+	/// Create new `Poe` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Poe { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber, Hash> PoeApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Hash>
+	for Poe<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber, Hash>,
+	AccountId: Codec,
+	BlockNumber: Codec,
+	Hash: Codec,
+{
+	fn verify_merkle_claim(
+		&self,
+		root: Hash,
+		leaf: Hash,
+		proof: Vec<Hash>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<(AccountId, BlockNumber)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.verify_merkle_claim(&at, root, leaf, proof).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(RUNTIME_ERROR),
+			message: "Unable to verify the Merkle claim.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
@@ -0,0 +1,213 @@
+//! Benchmarking setup for pallet-poe
+
+use super::*;
+
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Hash;
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+#[allow(unused)]
+use crate::Pallet as Poe;
+
+const SEED: u32 = 0;
+
+// A distinct claim of the maximum allowed length
+fn claim<T: Config>(i: u32) -> Vec<u8> {
+	let mut claim = i.encode();
+	claim.resize(T::ClaimLengthLimit::get(), 0);
+	claim
+}
+
+// Store `c` claims owned by `owner` and return them
+fn store_claims<T: Config>(owner: &T::AccountId, c: u32) -> Vec<Vec<u8>> {
+	let claims: Vec<Vec<u8>> = (0..c).map(claim::<T>).collect();
+	for claim in claims.iter() {
+		let block = frame_system::Pallet::<T>::block_number();
+		Proofs::<T>::insert(claim, ClaimInfo {
+			owner: owner.clone(),
+			created_at: block,
+			updated_at: block,
+			metadata: Vec::new(),
+		});
+	}
+	claims
+}
+
+// Give `d` delegates to `claim`
+fn add_delegates<T: Config>(claim: &[u8], d: u32) {
+	for i in 0..d {
+		let delegate: T::AccountId = account("delegate", i, SEED);
+		Delegates::<T>::insert(claim, &delegate, ClaimPermissions { revoke: true, transfer: true });
+	}
+}
+
+benchmarks! {
+	create_claim {
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = claim::<T>(0);
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).map(|c| c.owner), Some(caller));
+	}
+
+	revoke_claim {
+		let d in 0 .. T::MaxDelegates::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = store_claims::<T>(&caller, 1).remove(0);
+		add_delegates::<T>(&claim, d);
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(!Proofs::<T>::contains_key(&claim));
+	}
+
+	transfer_claim {
+		let d in 0 .. T::MaxDelegates::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let claim = store_claims::<T>(&caller, 1).remove(0);
+		add_delegates::<T>(&claim, d);
+	}: _(RawOrigin::Signed(caller), claim.clone(), recipient.clone())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).map(|c| c.owner), Some(recipient));
+	}
+
+	create_claims {
+		let c in 1 .. T::MaxClaimsPerBatch::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let claims: Vec<Vec<u8>> = (0..c).map(claim::<T>).collect();
+	}: _(RawOrigin::Signed(caller.clone()), claims.clone())
+	verify {
+		for claim in claims.iter() {
+			assert_eq!(Proofs::<T>::get(claim).map(|c| c.owner), Some(caller.clone()));
+		}
+	}
+
+	// Every claim has `d` delegates
+	revoke_claims {
+		let c in 1 .. T::MaxClaimsPerBatch::get();
+		let d in 0 .. T::MaxDelegates::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let claims = store_claims::<T>(&caller, c);
+		for claim in claims.iter() {
+			add_delegates::<T>(claim, d);
+		}
+	}: _(RawOrigin::Signed(caller), claims.clone())
+	verify {
+		for claim in claims.iter() {
+			assert!(!Proofs::<T>::contains_key(claim));
+		}
+	}
+
+	// Every claim has `d` delegates
+	transfer_claims {
+		let c in 1 .. T::MaxClaimsPerBatch::get();
+		let d in 0 .. T::MaxDelegates::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let claims = store_claims::<T>(&caller, c);
+		for claim in claims.iter() {
+			add_delegates::<T>(claim, d);
+		}
+	}: _(RawOrigin::Signed(caller), claims.clone(), recipient.clone())
+	verify {
+		for claim in claims.iter() {
+			assert_eq!(Proofs::<T>::get(claim).map(|c| c.owner), Some(recipient.clone()));
+		}
+	}
+
+	create_merkle_claim {
+		let caller: T::AccountId = whitelisted_caller();
+		let root = T::Hashing::hash(&claim::<T>(0));
+	}: _(RawOrigin::Signed(caller.clone()), root)
+	verify {
+		assert_eq!(MerkleRoots::<T>::get(&root).map(|(owner, _)| owner), Some(caller));
+	}
+
+	set_claim_metadata {
+		let m in 0 .. T::MetadataLengthLimit::get() as u32;
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = store_claims::<T>(&caller, 1).remove(0);
+		let metadata = vec![1u8; m as usize];
+	}: _(RawOrigin::Signed(caller), claim.clone(), metadata.clone())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).map(|c| c.metadata), Some(metadata));
+	}
+
+	// The claim already has `d` other delegates
+	add_delegate {
+		let d in 0 .. T::MaxDelegates::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("new_delegate", 0, SEED);
+		let claim = store_claims::<T>(&caller, 1).remove(0);
+		add_delegates::<T>(&claim, d);
+		let permissions = ClaimPermissions { revoke: true, transfer: true };
+	}: _(RawOrigin::Signed(caller), claim.clone(), delegate.clone(), permissions)
+	verify {
+		assert_eq!(Delegates::<T>::get(&claim, &delegate), Some(permissions));
+	}
+
+	remove_delegate {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let claim = store_claims::<T>(&caller, 1).remove(0);
+		Delegates::<T>::insert(&claim, &delegate, ClaimPermissions { revoke: true, transfer: true });
+	}: _(RawOrigin::Signed(caller), claim.clone(), delegate.clone())
+	verify {
+		assert!(!Delegates::<T>::contains_key(&claim, &delegate));
+	}
+
+	set_co_owners {
+		let o in 1 .. T::MaxCoOwners::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = store_claims::<T>(&caller, 1).remove(0);
+		let co_owners: Vec<T::AccountId> = (0..o).map(|i| account("co_owner", i, SEED)).collect();
+	}: _(RawOrigin::Signed(caller), claim.clone(), co_owners, o + 1)
+	verify {
+		assert!(CoOwners::<T>::contains_key(&claim));
+	}
+
+	// The approval reaching the threshold, which revokes the claim
+	approve_revoke {
+		let o in 2 .. T::MaxCoOwners::get();
+		let d in 0 .. T::MaxDelegates::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let claim = store_claims::<T>(&caller, 1).remove(0);
+		add_delegates::<T>(&claim, d);
+		let co_owners: Vec<T::AccountId> = (1..o).map(|i| account("co_owner", i, SEED)).collect();
+		let mut owners = vec![caller.clone()];
+		owners.extend(co_owners.iter().cloned());
+		CoOwners::<T>::insert(&claim, CoOwnership { owners, threshold: o });
+		RevokeApprovals::<T>::insert(&claim, co_owners);
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(!Proofs::<T>::contains_key(&claim));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_claim::<Test>());
+			assert_ok!(test_benchmark_revoke_claim::<Test>());
+			assert_ok!(test_benchmark_transfer_claim::<Test>());
+			assert_ok!(test_benchmark_create_claims::<Test>());
+			assert_ok!(test_benchmark_revoke_claims::<Test>());
+			assert_ok!(test_benchmark_transfer_claims::<Test>());
+			assert_ok!(test_benchmark_create_merkle_claim::<Test>());
+			assert_ok!(test_benchmark_set_claim_metadata::<Test>());
+			assert_ok!(test_benchmark_add_delegate::<Test>());
+			assert_ok!(test_benchmark_remove_delegate::<Test>());
+			assert_ok!(test_benchmark_set_co_owners::<Test>());
+			assert_ok!(test_benchmark_approve_revoke::<Test>());
+		});
+	}
+}
@@ -4,6 +4,12 @@
 mod mock;
 #[cfg(test)]
 mod tests;
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
 
 /// This module is for proof of existence
 pub use pallet::*;
@@ -15,7 +21,58 @@ pub mod pallet {
     pallet_prelude::*
   };
   use frame_system::pallet_prelude::*;
-  use sp_std::vec::Vec;
+  use sp_runtime::{traits::Hash, RuntimeDebug};
+  use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+  pub use crate::weights::WeightInfo;
+
+  /// Hashed before a document to make a leaf of a Merkle tree.
+  const MERKLE_LEAF_PREFIX: u8 = 0;
+  /// Hashed before two sibling nodes to make their parent, so inner nodes never pass for leaves.
+  const MERKLE_NODE_PREFIX: u8 = 1;
+
+  /// A notarized claim.
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+  pub struct ClaimInfo<AccountId, BlockNumber> {
+    pub owner: AccountId,
+    /// Block in which the claim was created.
+    pub created_at: BlockNumber,
+    /// Block of the last transfer or metadata update.
+    pub updated_at: BlockNumber,
+    /// Free-form data attached by the owner.
+    pub metadata: Vec<u8>,
+  }
+
+  pub type ClaimInfoOf<T> =
+    ClaimInfo<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
+  /// Storage layouts of the pallet, used to decide which migrations to run.
+  #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+  pub enum Releases {
+    /// `Proofs` values are `(owner, block_number)` tuples.
+    V1,
+    /// `Proofs` values are `ClaimInfo` structs.
+    V2,
+  }
+
+  impl Default for Releases {
+    fn default() -> Self {
+      Releases::V1
+    }
+  }
+
+  /// What a delegate is allowed to do with a claim on behalf of its owner.
+  #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+  pub struct ClaimPermissions {
+    pub revoke: bool,
+    pub transfer: bool,
+  }
+
+  /// The owners of a claim and how many of them must approve its revocation.
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+  pub struct CoOwnership<AccountId> {
+    pub owners: Vec<AccountId>,
+    pub threshold: u32,
+  }
 
   #[pallet::config]
   pub trait Config: frame_system::Config {
@@ -23,6 +80,23 @@ pub mod pallet {
 
 	type ClaimLengthLimit: Get<usize>;
 
+	/// Maximum length of the metadata attached to a claim.
+	type MetadataLengthLimit: Get<usize>;
+
+	/// Maximum number of claims accepted by a single batch call.
+	#[pallet::constant]
+	type MaxClaimsPerBatch: Get<u32>;
+
+	/// Maximum number of owners of a multi-owner claim, the original owner included.
+	#[pallet::constant]
+	type MaxCoOwners: Get<u32>;
+
+	/// Maximum number of delegates of a claim.
+	#[pallet::constant]
+	type MaxDelegates: Get<u32>;
+
+	/// Information on runtime weights.
+	type WeightInfo: WeightInfo;
   }
 
   #[pallet::pallet]
@@ -35,16 +109,76 @@ pub mod pallet {
     _,
     Blake2_128Concat,
     Vec<u8>,
+    ClaimInfoOf<T>
+  >;
+
+  /// Layout of the pallet storage. Chains started before `ClaimInfo` have no value and
+  /// default to `Releases::V1`.
+  #[pallet::storage]
+  pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+  /// Accounts allowed to act on a claim on behalf of its owner.
+  #[pallet::storage]
+  #[pallet::getter(fn delegates)]
+  pub type Delegates<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    Vec<u8>,
+    Blake2_128Concat,
+    T::AccountId,
+    ClaimPermissions
+  >;
+
+  /// Claims owned by several accounts. They can only be revoked through `approve_revoke`.
+  #[pallet::storage]
+  #[pallet::getter(fn co_owners)]
+  pub type CoOwners<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    Vec<u8>,
+    CoOwnership<T::AccountId>
+  >;
+
+  /// Co-owners who approved the revocation of a multi-owner claim so far.
+  #[pallet::storage]
+  #[pallet::getter(fn revoke_approvals)]
+  pub type RevokeApprovals<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    Vec<u8>,
+    Vec<T::AccountId>,
+    ValueQuery
+  >;
+
+  /// Merkle roots notarizing a whole set of documents, each document being a leaf of the tree.
+  #[pallet::storage]
+  #[pallet::getter(fn merkle_roots)]
+  pub type MerkleRoots<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    T::Hash,
     (T::AccountId, T::BlockNumber)
   >;
 
   #[pallet::event]
-  #[pallet::metadata(T::AccountId = "AccountId")]
+  #[pallet::metadata(T::AccountId = "AccountId", T::Hash = "Hash")]
   #[pallet::generate_deposit(pub(super) fn deposit_event)]
   pub enum Event<T: Config> {
     ClaimCreated(T::AccountId, Vec<u8>),
     ClaimRevoked(T::AccountId, Vec<u8>),
     ClaimTransferred(T::AccountId, T::AccountId, Vec<u8>),
+    /// A Merkle root was notarized. [owner, root]
+    MerkleClaimCreated(T::AccountId, T::Hash),
+    /// The owner attached metadata to a claim. [owner, claim]
+    ClaimMetadataSet(T::AccountId, Vec<u8>),
+    /// A delegate was granted permissions on a claim. [owner, delegate, claim, permissions]
+    DelegateAdded(T::AccountId, T::AccountId, Vec<u8>, ClaimPermissions),
+    /// A delegate was removed from a claim. [owner, delegate, claim]
+    DelegateRemoved(T::AccountId, T::AccountId, Vec<u8>),
+    /// A claim became a multi-owner claim. [claim, owners, threshold]
+    CoOwnersSet(Vec<u8>, Vec<T::AccountId>, u32),
+    /// A co-owner approved the revocation of a claim. [co-owner, claim, approvals]
+    RevokeApproved(T::AccountId, Vec<u8>, u32),
   }
 
   #[pallet::error]
@@ -52,15 +186,63 @@ pub mod pallet {
     ProofAlreadyExist,
     ClaimNotExist,
     NotClaimOwner,
-	ClaimLengthLimitExceed
+	ClaimLengthLimitExceed,
+	/// The metadata is longer than `MetadataLengthLimit`.
+	MetadataLengthLimitExceed,
+	/// The batch is empty or holds more than `MaxClaimsPerBatch` claims.
+	InvalidBatchSize,
+	/// The same claim appears more than once in a batch.
+	DuplicateClaimInBatch,
+	/// The Merkle root has already been notarized.
+	MerkleRootAlreadyExist,
+	/// The claim has several owners and needs `approve_revoke` from enough of them.
+	MultiOwnerClaim,
+	/// The account is not one of the owners of the claim.
+	NotCoOwner,
+	/// The account has already approved the revocation of the claim.
+	AlreadyApproved,
+	/// More co-owners than `MaxCoOwners`, or the same owner given twice.
+	InvalidCoOwners,
+	/// The threshold is zero or greater than the number of owners.
+	InvalidThreshold,
+	/// The account is not a delegate of the claim.
+	DelegateNotExist,
+	/// The claim already has `MaxDelegates` delegates.
+	TooManyDelegates,
+  }
+
+  #[pallet::genesis_config]
+  #[derive(Default)]
+  pub struct GenesisConfig {}
+
+  #[pallet::genesis_build]
+  impl<T: Config> GenesisBuild<T> for GenesisConfig {
+    fn build(&self) {
+      // New chains start with the latest layout
+      StorageVersion::<T>::put(Releases::V2);
+    }
   }
 
   #[pallet::hooks]
-  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn on_runtime_upgrade() -> Weight {
+      // frame-support 3.0.0 has no `pre_upgrade`/`post_upgrade` hooks, so the
+      // checks run around the migration itself when built with `try-runtime`
+      #[cfg(feature = "try-runtime")]
+      crate::migrations::v2::pre_migrate::<T>().expect("pallet-poe pre-upgrade check failed");
+
+      let weight = crate::migrations::v2::migrate::<T>();
+
+      #[cfg(feature = "try-runtime")]
+      crate::migrations::v2::post_migrate::<T>().expect("pallet-poe post-upgrade check failed");
+
+      weight
+    }
+  }
 
   #[pallet::call]
   impl<T: Config> Pallet<T> {
-    #[pallet::weight(0)]
+    #[pallet::weight(T::WeightInfo::create_claim())]
     pub fn create_claim(
       origin: OriginFor<T>,
       claim: Vec<u8>
@@ -72,13 +254,20 @@ pub mod pallet {
 
       ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
 
-      Proofs::<T>::insert(&claim, (sender.clone(), frame_system::Pallet::<T>::block_number()));
+      let cur_block = frame_system::Pallet::<T>::block_number();
+
+      Proofs::<T>::insert(&claim, ClaimInfo {
+        owner: sender.clone(),
+        created_at: cur_block,
+        updated_at: cur_block,
+        metadata: Vec::new(),
+      });
 
       Self::deposit_event(Event::ClaimCreated(sender, claim));
       Ok(().into())
     }
 
-    #[pallet::weight(0)]
+    #[pallet::weight(T::WeightInfo::revoke_claim(T::MaxDelegates::get()))]
     pub fn revoke_claim(
       origin: OriginFor<T>,
       claim: Vec<u8>
@@ -86,17 +275,15 @@ pub mod pallet {
 
       let sender = ensure_signed(origin)?;
 
-      let (owner, _) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
-
-      ensure!(owner == sender, Error::<T>::NotClaimOwner);
+      Self::ensure_authorized(&claim, &sender, |p| p.revoke)?;
 
-      Proofs::<T>::remove(&claim);
+      Self::remove_claim(&claim);
 
       Self::deposit_event(Event::ClaimRevoked(sender, claim));
       Ok(().into())
     }
 
-    #[pallet::weight(0)]
+    #[pallet::weight(T::WeightInfo::transfer_claim(T::MaxDelegates::get()))]
     pub fn transfer_claim(
       origin: OriginFor<T>,
       claim: Vec<u8>,
@@ -105,11 +292,8 @@ pub mod pallet {
 
       let sender = ensure_signed(origin)?;
 
-	  // Ensure the claim is existing
-      let (owner, _) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
-
-	  // Ensure the transaction sender is the claim owner
-      ensure!(owner == sender, Error::<T>::NotClaimOwner);
+	  // Ensure the claim is existing and the transaction sender is its owner or a delegate
+      Self::ensure_authorized(&claim, &sender, |p| p.transfer)?;
 
 	  // Get the current block number
 	  let cur_block = frame_system::Pallet::<T>::block_number();
@@ -118,17 +302,375 @@ pub mod pallet {
       Proofs::<T>::mutate(&claim, | value | {
 		  match value.as_mut() {
 		   Some(v) => {
-			   v.0 = recipient.clone();
-			   v.1 = cur_block;
+			   v.owner = recipient.clone();
+			   v.updated_at = cur_block;
 		   },
 		   None => {}
 		  }
 	  });
 
+	  // Delegates were granted by the previous owner
+	  Delegates::<T>::remove_prefix(&claim);
+
       Self::deposit_event(Event::ClaimTransferred(sender, recipient, claim));
       Ok(().into())
     }
 
+    /// Create several claims at once. Either every claim is created or none is.
+    #[pallet::weight(T::WeightInfo::create_claims(Pallet::<T>::batch_len(&claims)))]
+    pub fn create_claims(
+      origin: OriginFor<T>,
+      claims: Vec<Vec<u8>>
+    ) -> DispatchResultWithPostInfo {
+
+      let sender = ensure_signed(origin)?;
+
+      Self::ensure_valid_batch(&claims)?;
+
+	  // Validate every claim before touching storage
+      for claim in claims.iter() {
+        ensure!(!Proofs::<T>::contains_key(claim), Error::<T>::ProofAlreadyExist);
+      }
+
+      let cur_block = frame_system::Pallet::<T>::block_number();
+
+      for claim in claims {
+        Proofs::<T>::insert(&claim, ClaimInfo {
+          owner: sender.clone(),
+          created_at: cur_block,
+          updated_at: cur_block,
+          metadata: Vec::new(),
+        });
+        Self::deposit_event(Event::ClaimCreated(sender.clone(), claim));
+      }
+
+      Ok(().into())
+    }
+
+    /// Revoke several claims at once. Either every claim is revoked or none is.
+    #[pallet::weight(T::WeightInfo::revoke_claims(Pallet::<T>::batch_len(&claims), T::MaxDelegates::get()))]
+    pub fn revoke_claims(
+      origin: OriginFor<T>,
+      claims: Vec<Vec<u8>>
+    ) -> DispatchResultWithPostInfo {
+
+      let sender = ensure_signed(origin)?;
+
+      Self::ensure_valid_batch(&claims)?;
+
+	  // Validate every claim before touching storage
+      for claim in claims.iter() {
+        Self::ensure_authorized(claim, &sender, |p| p.revoke)?;
+      }
+
+      for claim in claims {
+        Self::remove_claim(&claim);
+        Self::deposit_event(Event::ClaimRevoked(sender.clone(), claim));
+      }
+
+      Ok(().into())
+    }
+
+    /// Transfer several claims to the same recipient at once. Either every claim is
+    /// transferred or none is.
+    #[pallet::weight(T::WeightInfo::transfer_claims(Pallet::<T>::batch_len(&claims), T::MaxDelegates::get()))]
+    pub fn transfer_claims(
+      origin: OriginFor<T>,
+      claims: Vec<Vec<u8>>,
+      recipient: T::AccountId
+    ) -> DispatchResultWithPostInfo {
+
+      let sender = ensure_signed(origin)?;
+
+      Self::ensure_valid_batch(&claims)?;
+
+	  // Validate every claim before touching storage
+      for claim in claims.iter() {
+        Self::ensure_authorized(claim, &sender, |p| p.transfer)?;
+      }
+
+      let cur_block = frame_system::Pallet::<T>::block_number();
+
+      for claim in claims {
+        Proofs::<T>::mutate(&claim, |value| {
+          if let Some(v) = value.as_mut() {
+            v.owner = recipient.clone();
+            v.updated_at = cur_block;
+          }
+        });
+        Delegates::<T>::remove_prefix(&claim);
+        Self::deposit_event(Event::ClaimTransferred(sender.clone(), recipient.clone(), claim));
+      }
+
+      Ok(().into())
+    }
+
+    /// Notarize a set of documents by storing the root of the Merkle tree built over them.
+    #[pallet::weight(T::WeightInfo::create_merkle_claim())]
+    pub fn create_merkle_claim(
+      origin: OriginFor<T>,
+      root: T::Hash
+    ) -> DispatchResultWithPostInfo {
+
+      let sender = ensure_signed(origin)?;
+
+      ensure!(!MerkleRoots::<T>::contains_key(&root), Error::<T>::MerkleRootAlreadyExist);
+
+      MerkleRoots::<T>::insert(&root, (sender.clone(), frame_system::Pallet::<T>::block_number()));
+
+      Self::deposit_event(Event::MerkleClaimCreated(sender, root));
+      Ok(().into())
+    }
+
+    /// Attach metadata to a claim, replacing any previous metadata.
+    #[pallet::weight(T::WeightInfo::set_claim_metadata(
+      metadata.len().min(T::MetadataLengthLimit::get()) as u32
+    ))]
+    pub fn set_claim_metadata(
+      origin: OriginFor<T>,
+      claim: Vec<u8>,
+      metadata: Vec<u8>
+    ) -> DispatchResultWithPostInfo {
+
+      let sender = ensure_signed(origin)?;
+
+      ensure!(metadata.len() <= T::MetadataLengthLimit::get(), Error::<T>::MetadataLengthLimitExceed);
+
+      Self::ensure_claim_owner(&claim, &sender)?;
+      ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::MultiOwnerClaim);
+
+      let cur_block = frame_system::Pallet::<T>::block_number();
+
+      Proofs::<T>::mutate(&claim, |value| {
+        if let Some(v) = value.as_mut() {
+          v.metadata = metadata;
+          v.updated_at = cur_block;
+        }
+      });
+
+      Self::deposit_event(Event::ClaimMetadataSet(sender, claim));
+      Ok(().into())
+    }
+
+    /// Allow `delegate` to revoke and/or transfer a claim on behalf of its owner. Calling it
+    /// again for the same delegate replaces the permissions. A claim has at most
+    /// `MaxDelegates` delegates.
+    #[pallet::weight(T::WeightInfo::add_delegate(T::MaxDelegates::get()))]
+    pub fn add_delegate(
+      origin: OriginFor<T>,
+      claim: Vec<u8>,
+      delegate: T::AccountId,
+      permissions: ClaimPermissions
+    ) -> DispatchResultWithPostInfo {
+
+      let sender = ensure_signed(origin)?;
+
+      Self::ensure_claim_owner(&claim, &sender)?;
+      ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::MultiOwnerClaim);
+
+      ensure!(
+        Delegates::<T>::contains_key(&claim, &delegate) ||
+          (Delegates::<T>::iter_prefix(&claim).count() as u32) < T::MaxDelegates::get(),
+        Error::<T>::TooManyDelegates
+      );
+
+      Delegates::<T>::insert(&claim, &delegate, permissions);
+
+      Self::deposit_event(Event::DelegateAdded(sender, delegate, claim, permissions));
+      Ok(().into())
+    }
+
+    #[pallet::weight(T::WeightInfo::remove_delegate())]
+    pub fn remove_delegate(
+      origin: OriginFor<T>,
+      claim: Vec<u8>,
+      delegate: T::AccountId
+    ) -> DispatchResultWithPostInfo {
+
+      let sender = ensure_signed(origin)?;
+
+      Self::ensure_claim_owner(&claim, &sender)?;
+      ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::MultiOwnerClaim);
+
+      ensure!(Delegates::<T>::contains_key(&claim, &delegate), Error::<T>::DelegateNotExist);
+
+      Delegates::<T>::remove(&claim, &delegate);
+
+      Self::deposit_event(Event::DelegateRemoved(sender, delegate, claim));
+      Ok(().into())
+    }
+
+    /// Share a claim with `co_owners`. From then on no single account can revoke, transfer
+    /// or edit the claim or its delegates; it is revoked once `threshold` owners called
+    /// `approve_revoke`.
+    #[pallet::weight(T::WeightInfo::set_co_owners(
+      co_owners.len().min(T::MaxCoOwners::get() as usize) as u32
+    ))]
+    pub fn set_co_owners(
+      origin: OriginFor<T>,
+      claim: Vec<u8>,
+      co_owners: Vec<T::AccountId>,
+      threshold: u32
+    ) -> DispatchResultWithPostInfo {
+
+      let sender = ensure_signed(origin)?;
+
+      // The sender is an owner too
+      ensure!(co_owners.len() < T::MaxCoOwners::get() as usize, Error::<T>::InvalidCoOwners);
+
+      Self::ensure_claim_owner(&claim, &sender)?;
+
+      ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::MultiOwnerClaim);
+
+      let mut owners = Vec::with_capacity(co_owners.len() + 1);
+      owners.push(sender);
+      owners.extend(co_owners);
+
+      let unique: BTreeSet<&T::AccountId> = owners.iter().collect();
+      ensure!(unique.len() == owners.len(), Error::<T>::InvalidCoOwners);
+      ensure!(threshold > 0 && threshold as usize <= owners.len(), Error::<T>::InvalidThreshold);
+
+      CoOwners::<T>::insert(&claim, CoOwnership { owners: owners.clone(), threshold });
+
+      Self::deposit_event(Event::CoOwnersSet(claim, owners, threshold));
+      Ok(().into())
+    }
+
+    /// Approve the revocation of a multi-owner claim. The claim is revoked by the approval
+    /// that reaches the threshold.
+    #[pallet::weight(T::WeightInfo::approve_revoke(T::MaxCoOwners::get(), T::MaxDelegates::get()))]
+    pub fn approve_revoke(
+      origin: OriginFor<T>,
+      claim: Vec<u8>
+    ) -> DispatchResultWithPostInfo {
+
+      let sender = ensure_signed(origin)?;
+
+      ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+      let co_ownership = CoOwners::<T>::get(&claim).ok_or(Error::<T>::NotCoOwner)?;
+
+      ensure!(co_ownership.owners.contains(&sender), Error::<T>::NotCoOwner);
+
+      let mut approvals = RevokeApprovals::<T>::get(&claim);
+
+      ensure!(!approvals.contains(&sender), Error::<T>::AlreadyApproved);
+
+      approvals.push(sender.clone());
+
+      if approvals.len() as u32 >= co_ownership.threshold {
+        Self::remove_claim(&claim);
+        Self::deposit_event(Event::ClaimRevoked(sender, claim));
+      } else {
+        let count = approvals.len() as u32;
+        RevokeApprovals::<T>::insert(&claim, approvals);
+        Self::deposit_event(Event::RevokeApproved(sender, claim, count));
+      }
+
+      Ok(().into())
+    }
+
+  }
+
+  impl<T: Config> Pallet<T> {
+    /// Check that `leaf` belongs to the tree notarized under `root` and return the owner and
+    /// block number of the Merkle claim.
+    ///
+    /// `leaf` is the hash of a document and `proof` holds the sibling hashes from the leaf up
+    /// to the root, so a tree needs at least two documents. Leaves are hashed as
+    /// `hash(0u8, leaf)` and inner nodes as `hash(1u8, smaller, larger)`: no position bits are
+    /// needed and an inner node can never pass for a document.
+    pub fn verify_merkle_claim(
+      root: T::Hash,
+      leaf: T::Hash,
+      proof: Vec<T::Hash>
+    ) -> Option<(T::AccountId, T::BlockNumber)> {
+      if proof.is_empty() {
+        return None;
+      }
+
+      let claim = MerkleRoots::<T>::get(&root)?;
+
+      let computed = proof.into_iter().fold(Self::hash_leaf(leaf), Self::hash_pair);
+
+      if computed == root {
+        Some(claim)
+      } else {
+        None
+      }
+    }
+
+    // Hash a document into a leaf of a Merkle tree
+    pub(crate) fn hash_leaf(leaf: T::Hash) -> T::Hash {
+      T::Hashing::hash_of(&(MERKLE_LEAF_PREFIX, leaf))
+    }
+
+    // Hash two sibling nodes of a Merkle tree in sorted order
+    pub(crate) fn hash_pair(a: T::Hash, b: T::Hash) -> T::Hash {
+      if a <= b {
+        T::Hashing::hash_of(&(MERKLE_NODE_PREFIX, a, b))
+      } else {
+        T::Hashing::hash_of(&(MERKLE_NODE_PREFIX, b, a))
+      }
+    }
+
+    // Size of a batch for weight purposes. Larger batches are rejected before any storage
+    // access, so they are only charged for `MaxClaimsPerBatch` claims.
+    fn batch_len(claims: &[Vec<u8>]) -> u32 {
+      claims.len().min(T::MaxClaimsPerBatch::get() as usize) as u32
+    }
+
+    // Check the batch size, the length of every claim and that no claim is repeated
+    fn ensure_valid_batch(claims: &[Vec<u8>]) -> Result<(), Error<T>> {
+      ensure!(
+        !claims.is_empty() && claims.len() <= T::MaxClaimsPerBatch::get() as usize,
+        Error::<T>::InvalidBatchSize
+      );
+
+      let mut seen = BTreeSet::new();
+      for claim in claims.iter() {
+        ensure!(claim.len() <= T::ClaimLengthLimit::get(), Error::<T>::ClaimLengthLimitExceed);
+        ensure!(seen.insert(claim), Error::<T>::DuplicateClaimInBatch);
+      }
+
+      Ok(())
+    }
+
+    // Ensure the claim exists and belongs to `who`
+    fn ensure_claim_owner(claim: &[u8], who: &T::AccountId) -> Result<(), Error<T>> {
+      let owner = Proofs::<T>::get(claim).ok_or(Error::<T>::ClaimNotExist)?.owner;
+      ensure!(&owner == who, Error::<T>::NotClaimOwner);
+      Ok(())
+    }
+
+    // Ensure `who` may act on a single-owner claim, either as its owner or as a delegate
+    // holding the permission selected by `allowed`
+    fn ensure_authorized(
+      claim: &[u8],
+      who: &T::AccountId,
+      allowed: fn(&ClaimPermissions) -> bool
+    ) -> Result<(), Error<T>> {
+      let owner = Proofs::<T>::get(claim).ok_or(Error::<T>::ClaimNotExist)?.owner;
+
+      ensure!(!CoOwners::<T>::contains_key(claim), Error::<T>::MultiOwnerClaim);
+
+      if &owner == who {
+        return Ok(());
+      }
+
+      match Delegates::<T>::get(claim, who) {
+        Some(permissions) if allowed(&permissions) => Ok(()),
+        _ => Err(Error::<T>::NotClaimOwner),
+      }
+    }
+
+    // Remove a claim together with its delegates and co-owners
+    fn remove_claim(claim: &[u8]) {
+      Proofs::<T>::remove(claim);
+      Delegates::<T>::remove_prefix(claim);
+      CoOwners::<T>::remove(claim);
+      RevokeApprovals::<T>::remove(claim);
+    }
   }
 
 }
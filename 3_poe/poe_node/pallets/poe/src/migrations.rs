@@ -0,0 +1,72 @@
+//! Storage migrations for pallet-poe.
+
+/// Migrate `Proofs` values from `(owner, block_number)` tuples to `ClaimInfo`.
+pub mod v2 {
+	use crate::pallet::{ClaimInfo, Config, Proofs, Releases, StorageVersion};
+	use frame_support::{traits::Get, weights::Weight};
+	use sp_std::vec::Vec;
+
+	#[cfg(feature = "try-runtime")]
+	const PRE_MIGRATION_COUNT: &[u8] = b":poe:v2:pre_migration_count:";
+
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::<T>::get() != Releases::V1 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut translated: Weight = 0;
+		Proofs::<T>::translate::<(T::AccountId, T::BlockNumber), _>(|_claim, (owner, block)| {
+			translated += 1;
+			Some(ClaimInfo {
+				owner,
+				created_at: block,
+				updated_at: block,
+				metadata: Vec::new(),
+			})
+		});
+
+		StorageVersion::<T>::put(Releases::V2);
+
+		T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		use frame_support::{storage::{migration::StorageIterator, unhashed}, traits::PalletInfo};
+
+		if StorageVersion::<T>::get() != Releases::V1 {
+			return Ok(());
+		}
+
+		let pallet = <T as frame_system::Config>::PalletInfo::name::<crate::Pallet<T>>()
+			.ok_or("pallet-poe is not part of the runtime")?;
+		let count = StorageIterator::<(T::AccountId, T::BlockNumber)>::new(pallet.as_bytes(), b"Proofs")
+			.count() as u32;
+
+		unhashed::put(PRE_MIGRATION_COUNT, &count);
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		use crate::pallet::ClaimInfoOf;
+		use frame_support::storage::unhashed;
+
+		if StorageVersion::<T>::get() != Releases::V2 {
+			return Err("pallet-poe storage version was not updated");
+		}
+
+		if let Some(count) = unhashed::take::<u32>(PRE_MIGRATION_COUNT) {
+			// Every claim must decode as `ClaimInfo` and be fresh from the migration
+			let migrated: Vec<ClaimInfoOf<T>> = Proofs::<T>::iter().map(|(_, c)| c).collect();
+			if migrated.len() as u32 != count {
+				return Err("some claims were lost by the migration");
+			}
+			if migrated.iter().any(|c| c.created_at != c.updated_at || !c.metadata.is_empty()) {
+				return Err("some claims were not migrated correctly");
+			}
+		}
+
+		Ok(())
+	}
+}
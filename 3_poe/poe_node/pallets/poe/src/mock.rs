@@ -17,7 +17,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
-		PoeModule: pallet_poe::{Module, Call, Storage, Event<T>},
+		PoeModule: pallet_poe::{Module, Call, Config, Storage, Event<T>},
 	}
 );
 
@@ -25,6 +25,10 @@ parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
 	pub const ClaimLengthLimit: usize = 2;
+	pub const MetadataLengthLimit: usize = 4;
+	pub const MaxClaimsPerBatch: u32 = 3;
+	pub const MaxCoOwners: u32 = 3;
+	pub const MaxDelegates: u32 = 2;
 }
 
 impl system::Config for Test {
@@ -55,6 +59,11 @@ impl system::Config for Test {
 impl pallet_poe::Config for Test {
 	type Event = Event;
 	type ClaimLengthLimit = ClaimLengthLimit;
+	type MetadataLengthLimit = MetadataLengthLimit;
+	type MaxClaimsPerBatch = MaxClaimsPerBatch;
+	type MaxCoOwners = MaxCoOwners;
+	type MaxDelegates = MaxDelegates;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,13 +1,25 @@
 use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use codec::Encode;
+use frame_support::{
+	assert_ok, assert_noop, dispatch::GetDispatchInfo, storage::migration::put_storage_value,
+	traits::OnRuntimeUpgrade, Blake2_128Concat, StorageHasher,
+};
 use super::*;
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+// A claim created and last updated in the current block
+fn claim_info(owner: u64) -> ClaimInfoOf<Test> {
+	let block = frame_system::Pallet::<Test>::block_number();
+	ClaimInfo { owner, created_at: block, updated_at: block, metadata: vec![] }
+}
 
 #[test]
 fn create_claim_succeeds() {
 	new_test_ext().execute_with(|| {
 		let claim = vec![0, 1];
 		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
-		assert_eq!(Proofs::<Test>::get(&claim), Some((1, frame_system::Pallet::<Test>::block_number())));
+		assert_eq!(Proofs::<Test>::get(&claim), Some(claim_info(1)));
 	})
 }
 
@@ -58,7 +70,7 @@ fn transfer_claim_succeeds() {
 		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
 
 		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
-		assert_eq!(Proofs::<Test>::get(&claim), Some((2, frame_system::Pallet::<Test>::block_number())));
+		assert_eq!(Proofs::<Test>::get(&claim), Some(claim_info(2)));
 	})
 }
 
@@ -80,3 +92,382 @@ fn transfer_claim_fails_with_wrong_claim_owner() {
 		assert_noop!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2), Error::<Test>::NotClaimOwner);
 	})
 }
+
+#[test]
+fn create_claims_succeeds() {
+	new_test_ext().execute_with(|| {
+		let claims = vec![vec![0, 1], vec![0, 2], vec![0, 3]];
+		assert_ok!(PoeModule::create_claims(Origin::signed(1), claims.clone()));
+		for claim in claims.iter() {
+			assert_eq!(Proofs::<Test>::get(claim), Some(claim_info(1)));
+		}
+	})
+}
+
+#[test]
+fn create_claims_fails_with_invalid_batch_size() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(PoeModule::create_claims(Origin::signed(1), vec![]), Error::<Test>::InvalidBatchSize);
+
+		let claims = vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![0, 4]];
+		assert_noop!(PoeModule::create_claims(Origin::signed(1), claims.clone()), Error::<Test>::InvalidBatchSize);
+
+		// Oversized batches are only charged for the largest accepted batch
+		let weight = crate::Call::<Test>::create_claims(claims).get_dispatch_info().weight;
+		assert_eq!(weight, <() as WeightInfo>::create_claims(MaxClaimsPerBatch::get()));
+	})
+}
+
+#[test]
+fn create_claims_fails_with_limit_exceeding() {
+	new_test_ext().execute_with(|| {
+		let claims = vec![vec![0, 1], vec![0, 1, 2]];
+		assert_noop!(PoeModule::create_claims(Origin::signed(1), claims), Error::<Test>::ClaimLengthLimitExceed);
+	})
+}
+
+#[test]
+fn create_claims_fails_with_duplicate_claim() {
+	new_test_ext().execute_with(|| {
+		let claims = vec![vec![0, 1], vec![0, 1]];
+		assert_noop!(PoeModule::create_claims(Origin::signed(1), claims), Error::<Test>::DuplicateClaimInBatch);
+	})
+}
+
+#[test]
+fn create_claims_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		let _ = PoeModule::create_claim(Origin::signed(1), vec![0, 2]);
+
+		let claims = vec![vec![0, 1], vec![0, 2]];
+		assert_noop!(PoeModule::create_claims(Origin::signed(1), claims), Error::<Test>::ProofAlreadyExist);
+		assert_eq!(Proofs::<Test>::get(&vec![0u8, 1]), None);
+	})
+}
+
+#[test]
+fn revoke_claims_succeeds() {
+	new_test_ext().execute_with(|| {
+		let claims = vec![vec![0, 1], vec![0, 2]];
+		let _ = PoeModule::create_claims(Origin::signed(1), claims.clone());
+
+		assert_ok!(PoeModule::revoke_claims(Origin::signed(1), claims.clone()));
+		for claim in claims.iter() {
+			assert_eq!(Proofs::<Test>::get(claim), None);
+		}
+	})
+}
+
+#[test]
+fn revoke_claims_fails_with_wrong_claim_owner() {
+	new_test_ext().execute_with(|| {
+		let _ = PoeModule::create_claim(Origin::signed(1), vec![0, 1]);
+		let _ = PoeModule::create_claim(Origin::signed(6), vec![0, 2]);
+
+		let claims = vec![vec![0, 1], vec![0, 2]];
+		assert_noop!(PoeModule::revoke_claims(Origin::signed(1), claims), Error::<Test>::NotClaimOwner);
+	})
+}
+
+#[test]
+fn revoke_claims_fails_with_claim_not_existing() {
+	new_test_ext().execute_with(|| {
+		let _ = PoeModule::create_claim(Origin::signed(1), vec![0, 1]);
+
+		let claims = vec![vec![0, 1], vec![0, 2]];
+		assert_noop!(PoeModule::revoke_claims(Origin::signed(1), claims), Error::<Test>::ClaimNotExist);
+	})
+}
+
+#[test]
+fn transfer_claims_succeeds() {
+	new_test_ext().execute_with(|| {
+		let claims = vec![vec![0, 1], vec![0, 2]];
+		let _ = PoeModule::create_claims(Origin::signed(1), claims.clone());
+
+		assert_ok!(PoeModule::transfer_claims(Origin::signed(1), claims.clone(), 2));
+		for claim in claims.iter() {
+			assert_eq!(Proofs::<Test>::get(claim), Some(claim_info(2)));
+		}
+	})
+}
+
+#[test]
+fn transfer_claims_fails_with_wrong_claim_owner() {
+	new_test_ext().execute_with(|| {
+		let _ = PoeModule::create_claim(Origin::signed(1), vec![0, 1]);
+		let _ = PoeModule::create_claim(Origin::signed(6), vec![0, 2]);
+
+		let claims = vec![vec![0, 1], vec![0, 2]];
+		assert_noop!(PoeModule::transfer_claims(Origin::signed(1), claims, 2), Error::<Test>::NotClaimOwner);
+	})
+}
+
+#[test]
+fn create_merkle_claim_succeeds() {
+	new_test_ext().execute_with(|| {
+		let root = H256::repeat_byte(1);
+		assert_ok!(PoeModule::create_merkle_claim(Origin::signed(1), root));
+		assert_eq!(MerkleRoots::<Test>::get(&root), Some((1, frame_system::Pallet::<Test>::block_number())));
+	})
+}
+
+#[test]
+fn create_merkle_claim_fails_with_root_existing() {
+	new_test_ext().execute_with(|| {
+		let root = H256::repeat_byte(1);
+		let _ = PoeModule::create_merkle_claim(Origin::signed(1), root);
+
+		assert_noop!(PoeModule::create_merkle_claim(Origin::signed(2), root), Error::<Test>::MerkleRootAlreadyExist);
+	})
+}
+
+#[test]
+fn verify_merkle_claim_works() {
+	new_test_ext().execute_with(|| {
+		// A tree over four documents
+		let documents: Vec<H256> = (0u8..4).map(|i| BlakeTwo256::hash(&[i])).collect();
+		let leaves: Vec<H256> = documents.iter().map(|d| PoeModule::hash_leaf(*d)).collect();
+		let node_01 = PoeModule::hash_pair(leaves[0], leaves[1]);
+		let node_23 = PoeModule::hash_pair(leaves[2], leaves[3]);
+		let root = PoeModule::hash_pair(node_01, node_23);
+
+		// Nothing is notarized yet
+		assert_eq!(PoeModule::verify_merkle_claim(root, documents[2], vec![leaves[3], node_01]), None);
+
+		let _ = PoeModule::create_merkle_claim(Origin::signed(1), root);
+
+		assert_eq!(
+			PoeModule::verify_merkle_claim(root, documents[2], vec![leaves[3], node_01]),
+			Some((1, frame_system::Pallet::<Test>::block_number()))
+		);
+		assert_eq!(
+			PoeModule::verify_merkle_claim(root, documents[1], vec![leaves[0], node_23]),
+			Some((1, frame_system::Pallet::<Test>::block_number()))
+		);
+
+		// Wrong sibling or a document outside of the tree
+		assert_eq!(PoeModule::verify_merkle_claim(root, documents[2], vec![leaves[0], node_01]), None);
+		assert_eq!(PoeModule::verify_merkle_claim(root, H256::repeat_byte(9), vec![leaves[3], node_01]), None);
+	})
+}
+
+#[test]
+fn verify_merkle_claim_rejects_inner_nodes_and_empty_proofs() {
+	new_test_ext().execute_with(|| {
+		let documents: Vec<H256> = (0u8..4).map(|i| BlakeTwo256::hash(&[i])).collect();
+		let leaves: Vec<H256> = documents.iter().map(|d| PoeModule::hash_leaf(*d)).collect();
+		let node_01 = PoeModule::hash_pair(leaves[0], leaves[1]);
+		let node_23 = PoeModule::hash_pair(leaves[2], leaves[3]);
+		let root = PoeModule::hash_pair(node_01, node_23);
+		let _ = PoeModule::create_merkle_claim(Origin::signed(1), root);
+
+		// The root is not a document of its own tree
+		assert_eq!(PoeModule::verify_merkle_claim(root, root, vec![]), None);
+
+		// Neither is an inner node, nor a leaf already hashed as such
+		assert_eq!(PoeModule::verify_merkle_claim(root, node_01, vec![node_23]), None);
+		assert_eq!(PoeModule::verify_merkle_claim(root, leaves[0], vec![leaves[1], node_23]), None);
+
+		// A single document tree cannot be proven either
+		let single = PoeModule::hash_leaf(documents[0]);
+		let _ = PoeModule::create_merkle_claim(Origin::signed(1), single);
+		assert_eq!(PoeModule::verify_merkle_claim(single, documents[0], vec![]), None);
+	})
+}
+
+#[test]
+fn delegate_can_revoke_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		assert_ok!(PoeModule::add_delegate(
+			Origin::signed(1), claim.clone(), 2, ClaimPermissions { revoke: true, transfer: false }
+		));
+		assert_noop!(PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 3), Error::<Test>::NotClaimOwner);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert_eq!(Delegates::<Test>::get(&claim, 2u64), None);
+	})
+}
+
+#[test]
+fn delegate_can_transfer_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		assert_ok!(PoeModule::add_delegate(
+			Origin::signed(1), claim.clone(), 2, ClaimPermissions { revoke: false, transfer: true }
+		));
+		assert_noop!(PoeModule::revoke_claim(Origin::signed(2), claim.clone()), Error::<Test>::NotClaimOwner);
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 3));
+		assert_eq!(Proofs::<Test>::get(&claim), Some(claim_info(3)));
+		// Delegates of the previous owner are dropped
+		assert_eq!(Delegates::<Test>::get(&claim, 2u64), None);
+	})
+}
+
+#[test]
+fn add_delegate_fails_with_wrong_claim_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		assert_noop!(
+			PoeModule::add_delegate(Origin::signed(2), claim.clone(), 2, ClaimPermissions { revoke: true, transfer: true }),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+#[test]
+fn add_delegate_fails_with_too_many_delegates() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let permissions = ClaimPermissions { revoke: true, transfer: true };
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+		let _ = PoeModule::add_delegate(Origin::signed(1), claim.clone(), 2, permissions);
+		let _ = PoeModule::add_delegate(Origin::signed(1), claim.clone(), 3, permissions);
+
+		assert_noop!(
+			PoeModule::add_delegate(Origin::signed(1), claim.clone(), 4, permissions),
+			Error::<Test>::TooManyDelegates
+		);
+
+		// The permissions of an existing delegate can still be replaced
+		let transfer_only = ClaimPermissions { revoke: false, transfer: true };
+		assert_ok!(PoeModule::add_delegate(Origin::signed(1), claim.clone(), 3, transfer_only));
+		assert_eq!(Delegates::<Test>::get(&claim, 3u64), Some(transfer_only));
+	})
+}
+
+#[test]
+fn remove_delegate_succeeds() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+		let _ = PoeModule::add_delegate(Origin::signed(1), claim.clone(), 2, ClaimPermissions { revoke: true, transfer: true });
+
+		assert_ok!(PoeModule::remove_delegate(Origin::signed(1), claim.clone(), 2));
+		assert_noop!(PoeModule::revoke_claim(Origin::signed(2), claim.clone()), Error::<Test>::NotClaimOwner);
+		assert_noop!(PoeModule::remove_delegate(Origin::signed(1), claim.clone(), 2), Error::<Test>::DelegateNotExist);
+	})
+}
+
+#[test]
+fn set_co_owners_fails_with_invalid_parameters() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		assert_noop!(PoeModule::set_co_owners(Origin::signed(2), claim.clone(), vec![3], 1), Error::<Test>::NotClaimOwner);
+		assert_noop!(PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![1], 1), Error::<Test>::InvalidCoOwners);
+		assert_noop!(PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![2, 3, 4], 2), Error::<Test>::InvalidCoOwners);
+		assert_noop!(PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![2], 0), Error::<Test>::InvalidThreshold);
+		assert_noop!(PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![2], 3), Error::<Test>::InvalidThreshold);
+	})
+}
+
+#[test]
+fn multi_owner_claim_is_revoked_with_enough_approvals() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		assert_ok!(PoeModule::set_co_owners(Origin::signed(1), claim.clone(), vec![2, 3], 2));
+
+		// A single owner can no longer revoke or transfer the claim
+		assert_noop!(PoeModule::revoke_claim(Origin::signed(1), claim.clone()), Error::<Test>::MultiOwnerClaim);
+		assert_noop!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 4), Error::<Test>::MultiOwnerClaim);
+		assert_noop!(PoeModule::set_claim_metadata(Origin::signed(1), claim.clone(), vec![7]), Error::<Test>::MultiOwnerClaim);
+		assert_noop!(
+			PoeModule::add_delegate(Origin::signed(1), claim.clone(), 4, ClaimPermissions { revoke: true, transfer: true }),
+			Error::<Test>::MultiOwnerClaim
+		);
+		assert_noop!(PoeModule::remove_delegate(Origin::signed(1), claim.clone(), 4), Error::<Test>::MultiOwnerClaim);
+
+		assert_noop!(PoeModule::approve_revoke(Origin::signed(4), claim.clone()), Error::<Test>::NotCoOwner);
+
+		assert_ok!(PoeModule::approve_revoke(Origin::signed(2), claim.clone()));
+		assert_noop!(PoeModule::approve_revoke(Origin::signed(2), claim.clone()), Error::<Test>::AlreadyApproved);
+		assert!(Proofs::<Test>::get(&claim).is_some());
+
+		assert_ok!(PoeModule::approve_revoke(Origin::signed(3), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert_eq!(CoOwners::<Test>::get(&claim), None);
+		assert!(RevokeApprovals::<Test>::get(&claim).is_empty());
+	})
+}
+
+#[test]
+fn transfer_claim_keeps_creation_block() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		System::set_block_number(5);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo { owner: 2, created_at: 0, updated_at: 5, metadata: vec![] })
+		);
+	})
+}
+
+#[test]
+fn set_claim_metadata_succeeds() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		System::set_block_number(3);
+		assert_ok!(PoeModule::set_claim_metadata(Origin::signed(1), claim.clone(), vec![7, 7]));
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo { owner: 1, created_at: 0, updated_at: 3, metadata: vec![7, 7] })
+		);
+	})
+}
+
+#[test]
+fn set_claim_metadata_fails_with_invalid_input() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		assert_noop!(
+			PoeModule::set_claim_metadata(Origin::signed(1), claim.clone(), vec![0; 5]),
+			Error::<Test>::MetadataLengthLimitExceed
+		);
+		assert_noop!(PoeModule::set_claim_metadata(Origin::signed(2), claim.clone(), vec![7]), Error::<Test>::NotClaimOwner);
+	})
+}
+
+#[test]
+fn migration_to_claim_info_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0u8, 1];
+		let key = Blake2_128Concat::hash(&claim.encode());
+		put_storage_value(b"PoeModule", b"Proofs", &key, (1u64, 7u64));
+
+		assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
+		PoeModule::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo { owner: 1, created_at: 7, updated_at: 7, metadata: vec![] })
+		);
+
+		// Running it again is a no-op
+		PoeModule::on_runtime_upgrade();
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo { owner: 1, created_at: 7, updated_at: 7, metadata: vec![] })
+		);
+	})
+}
@@ -0,0 +1,254 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Autogenerated weights for pallet_poe
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
+//! DATE: 2026-10-19, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/node-template
+// benchmark
+// --chain
+// dev
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet
+// pallet_poe
+// --extrinsic
+// *
+// --steps
+// 50
+// --repeat
+// 20
+// --output
+// pallets/poe/src/weights.rs
+// --template
+// ../../8_benchmark/.maintain/frame-weight-template.hbs
+
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_poe.
+pub trait WeightInfo {
+	fn create_claim() -> Weight;
+	fn revoke_claim(d: u32, ) -> Weight;
+	fn transfer_claim(d: u32, ) -> Weight;
+	fn create_claims(c: u32, ) -> Weight;
+	fn revoke_claims(c: u32, d: u32, ) -> Weight;
+	fn transfer_claims(c: u32, d: u32, ) -> Weight;
+	fn create_merkle_claim() -> Weight;
+	fn set_claim_metadata(m: u32, ) -> Weight;
+	fn add_delegate(d: u32, ) -> Weight;
+	fn remove_delegate() -> Weight;
+	fn set_co_owners(o: u32, ) -> Weight;
+	fn approve_revoke(o: u32, d: u32, ) -> Weight;
+}
+
+/// Weights for pallet_poe using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn create_claim() -> Weight {
+		(37_348_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_claim(d: u32, ) -> Weight {
+		(60_255_000 as Weight)
+			// Standard Error: 123_000
+			.saturating_add((1_257_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+	fn transfer_claim(d: u32, ) -> Weight {
+		(44_666_000 as Weight)
+			// Standard Error: 110_000
+			.saturating_add((102_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+	fn create_claims(c: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 200_000
+			.saturating_add((17_704_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn revoke_claims(c: u32, d: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 427_000
+			.saturating_add((53_003_000 as Weight).saturating_mul(c as Weight))
+			// Standard Error: 7_416_000
+			.saturating_add((176_987_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((13 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
+	}
+	fn transfer_claims(c: u32, d: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 468_000
+			.saturating_add((80_883_000 as Weight).saturating_mul(c as Weight))
+			// Standard Error: 8_117_000
+			.saturating_add((256_729_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((11 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
+	}
+	fn create_merkle_claim() -> Weight {
+		(35_981_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_claim_metadata(m: u32, ) -> Weight {
+		(49_624_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((5_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_delegate(d: u32, ) -> Weight {
+		(64_680_000 as Weight)
+			// Standard Error: 223_000
+			.saturating_add((15_731_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_delegate() -> Weight {
+		(59_741_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_co_owners(o: u32, ) -> Weight {
+		(47_592_000 as Weight)
+			// Standard Error: 46_000
+			.saturating_add((475_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn approve_revoke(o: u32, d: u32, ) -> Weight {
+		(76_021_000 as Weight)
+			// Standard Error: 95_000
+			.saturating_add((167_000 as Weight).saturating_mul(o as Weight))
+			// Standard Error: 73_000
+			.saturating_add((2_302_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_claim() -> Weight {
+		(37_348_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_claim(d: u32, ) -> Weight {
+		(60_255_000 as Weight)
+			// Standard Error: 123_000
+			.saturating_add((1_257_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+	fn transfer_claim(d: u32, ) -> Weight {
+		(44_666_000 as Weight)
+			// Standard Error: 110_000
+			.saturating_add((102_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+	fn create_claims(c: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 200_000
+			.saturating_add((17_704_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn revoke_claims(c: u32, d: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 427_000
+			.saturating_add((53_003_000 as Weight).saturating_mul(c as Weight))
+			// Standard Error: 7_416_000
+			.saturating_add((176_987_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((13 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
+	}
+	fn transfer_claims(c: u32, d: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 468_000
+			.saturating_add((80_883_000 as Weight).saturating_mul(c as Weight))
+			// Standard Error: 8_117_000
+			.saturating_add((256_729_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((11 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
+	}
+	fn create_merkle_claim() -> Weight {
+		(35_981_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_claim_metadata(m: u32, ) -> Weight {
+		(49_624_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((5_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn add_delegate(d: u32, ) -> Weight {
+		(64_680_000 as Weight)
+			// Standard Error: 223_000
+			.saturating_add((15_731_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_delegate() -> Weight {
+		(59_741_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_co_owners(o: u32, ) -> Weight {
+		(47_592_000 as Weight)
+			// Standard Error: 46_000
+			.saturating_add((475_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn approve_revoke(o: u32, d: u32, ) -> Weight {
+		(76_021_000 as Weight)
+			// Standard Error: 95_000
+			.saturating_add((167_000 as Weight).saturating_mul(o as Weight))
+			// Standard Error: 73_000
+			.saturating_add((2_302_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+}
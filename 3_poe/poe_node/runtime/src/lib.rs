@@ -42,6 +42,9 @@ use pallet_transaction_payment::CurrencyAdapter;
 /// Import the template pallet.
 pub use pallet_template;
 
+/// Import the poe pallet.
+pub use pallet_poe;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -96,7 +99,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-template"),
 	impl_name: create_runtime_str!("node-template"),
 	authoring_version: 1,
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -139,6 +142,10 @@ parameter_types! {
 		::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
 	pub const SS58Prefix: u8 = 42;
 	pub const ClaimLengthLimit: usize = 100;
+	pub const MetadataLengthLimit: usize = 256;
+	pub const MaxClaimsPerBatch: u32 = 100;
+	pub const MaxCoOwners: u32 = 10;
+	pub const MaxDelegates: u32 = 10;
 }
 
 // Configure FRAME pallets to include in runtime.
@@ -269,6 +276,11 @@ impl pallet_template::Config for Runtime {
 impl pallet_poe::Config for Runtime {
 	type Event = Event;
 	type ClaimLengthLimit = ClaimLengthLimit;
+	type MetadataLengthLimit = MetadataLengthLimit;
+	type MaxClaimsPerBatch = MaxClaimsPerBatch;
+	type MaxCoOwners = MaxCoOwners;
+	type MaxDelegates = MaxDelegates;
+	type WeightInfo = pallet_poe::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -288,7 +300,7 @@ construct_runtime!(
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the template pallet in the runtime.
 		TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
-		PoeModule: pallet_poe::{Module, Call, Storage, Event<T>},
+		PoeModule: pallet_poe::{Module, Call, Config, Storage, Event<T>},
 	}
 );
 
@@ -457,6 +469,16 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_poe_runtime_api::PoeApi<Block, AccountId, BlockNumber, Hash> for Runtime {
+		fn verify_merkle_claim(
+			root: Hash,
+			leaf: Hash,
+			proof: Vec<Hash>,
+		) -> Option<(AccountId, BlockNumber)> {
+			PoeModule::verify_merkle_claim(root, leaf, proof)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -491,6 +513,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_poe, PoeModule);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -1,10 +1,10 @@
 use kitties_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, KittiesConfig, Signature,
+	SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{blake2_128, sr25519, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
@@ -31,6 +31,22 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
+/// Demo kitties for development chains: a female and a male for both Alice and Bob.
+pub fn demo_kitties() -> Vec<(AccountId, [u8; 16])> {
+	["Alice", "Bob"]
+		.iter()
+		.flat_map(|seed| {
+			let owner = get_account_id_from_seed::<sr25519::Public>(seed);
+			(0u8..2).map(move |gender| {
+				let mut dna = blake2_128(format!("{}//kitty//{}", seed, gender).as_bytes());
+				// The parity of the last byte is the gender, see `pallet_kitties::KittyTraits`
+				dna[15] = (dna[15] & !1) | gender;
+				(owner.clone(), dna)
+			})
+		})
+		.collect()
+}
+
 /// Generate an Aura authority key.
 pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
@@ -59,6 +75,8 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Pre-minted kitties
+				demo_kitties(),
 				true,
 			)
 		},
@@ -106,6 +124,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Pre-minted kitties
+				demo_kitties(),
 				true,
 			)
 		},
@@ -128,6 +148,7 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	kitties: Vec<(AccountId, [u8; 16])>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -150,5 +171,6 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		},
+		kitties: KittiesConfig { kitties },
 	}
 }
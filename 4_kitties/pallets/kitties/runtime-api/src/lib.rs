@@ -0,0 +1,25 @@
+//! Runtime API definition for pallet-kitties.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_kitties::{FamilyTree, Gender, KittyTraits};
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<AccountId, KittyIndex, BlockNumber> where
+		AccountId: Codec,
+		KittyIndex: Codec,
+		BlockNumber: Codec,
+	{
+		/// The indexes of all kitties owned by `owner`.
+		fn kitties_of(owner: AccountId) -> Vec<KittyIndex>;
+
+		/// The ancestors of a kitty, going at most `depth` generations back.
+		fn family_tree(kitty_id: KittyIndex, depth: u32) -> Option<FamilyTree<KittyIndex, BlockNumber>>;
+
+		/// The traits of a kitty decoded from its DNA.
+		fn kitty_traits(kitty_id: KittyIndex) -> Option<KittyTraits>;
+	}
+}
@@ -1,19 +1,495 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-kitties
 
 use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Kitties;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use crate::{pallet::BalanceOf, Gender};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get, Hooks};
 use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, StaticLookup};
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 4u32.into());
+	who
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 4u32.into());
+	caller
+}
+
+// Create `n` kitties for `owner` and return the index of the last one, without waiting for
+// `RevealDelay`
+fn create_kitties<T: Config>(owner: &T::AccountId, n: u32) -> T::KittyIndex {
+	for _ in 0..n {
+		let kitty_id = Kitties::<T>::next_kitty_id().unwrap();
+		let dna = Kitties::<T>::random_value(owner);
+		Kitties::<T>::kitty_create(owner.clone(), kitty_id, dna, None, 0).unwrap();
+	}
+	Kitties::<T>::kitties_count().unwrap()
+}
+
+// The kitty belongs to `owner`, or will once it is revealed
+fn assert_kitty_of<T: Config>(kitty_id: T::KittyIndex, owner: T::AccountId) {
+	let pending_owner = Kitties::<T>::pending_kitties(kitty_id).map(|pending| pending.owner);
+	assert_eq!(Kitties::<T>::owner(kitty_id).or(pending_owner), Some(owner));
+}
+
+// Overwrite the gender gene so that breeding is allowed
+fn set_gender<T: Config>(kitty_id: T::KittyIndex, gender: Gender) {
+	crate::Kitties::<T>::mutate(kitty_id, |kitty| {
+		if let Some(k) = kitty {
+			k.dna[15] = match gender {
+				Gender::Female => 0,
+				Gender::Male => 1,
+			};
+		}
+	});
+}
+
+fn lookup_of<T: Config>(who: &T::AccountId) -> <T::Lookup as StaticLookup>::Source {
+	T::Lookup::unlookup(who.clone())
+}
+
+// Fill `AuctionsEndingAt` for the block `duration` blocks from now, leaving room for one more
+fn fill_auctions<T: Config>(duration: T::BlockNumber) {
+	for i in 0..T::MaxAuctionsPerBlock::get().saturating_sub(1) {
+		let seller = funded_account::<T>("seller", i);
+		let kitty_id = create_kitties::<T>(&seller, 1);
+		Kitties::<T>::create_auction(
+			RawOrigin::Signed(seller).into(),
+			kitty_id,
+			1u32.into(),
+			duration,
+		)
+		.unwrap();
+	}
+}
+
+// Lease `n` kitties to distinct lessees, ending `duration` blocks from now
+fn create_leases<T: Config>(n: u32, duration: T::BlockNumber) {
+	for i in 0..n {
+		let owner = funded_account::<T>("lessor", i);
+		let kitty_id = create_kitties::<T>(&owner, 1);
+		let lessee = funded_account::<T>("lessee", i);
+		Kitties::<T>::lease(
+			RawOrigin::Signed(owner).into(),
+			kitty_id,
+			lookup_of::<T>(&lessee),
+			duration,
+			1u32.into(),
+		)
+		.unwrap();
+		Kitties::<T>::accept_lease(RawOrigin::Signed(lessee).into(), kitty_id, 1u32.into())
+			.unwrap();
+	}
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
-		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+	// The caller owns as many kitties as allowed, but one
+	create {
+		let caller = funded_caller::<T>();
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 1);
+		let kitty_id = Kitties::<T>::next_kitty_id().unwrap();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_kitty_of::<T>(kitty_id, caller);
+	}
+
+	transfer {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		let recipient = funded_account::<T>("recipient", 0);
+		create_kitties::<T>(&recipient, T::MaxKittiesOwned::get() - 1);
+	}: _(RawOrigin::Signed(caller), lookup_of::<T>(&recipient), kitty_id)
+	verify {
+		assert_eq!(Kitties::<T>::owner(kitty_id), Some(recipient));
+	}
+
+	approve {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		let approved: T::AccountId = account("approved", 0, SEED);
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(lookup_of::<T>(&approved)))
+	verify {
+		assert_eq!(Kitties::<T>::kitty_approval(kitty_id), Some(approved));
+	}
+
+	set_approval_for_all {
+		let caller = funded_caller::<T>();
+		let operator: T::AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), lookup_of::<T>(&operator), true)
+	verify {
+		assert_eq!(Kitties::<T>::operator_approval(&caller, &operator), Some(()));
+	}
+
+	// The caller is an operator, the most expensive permission to check
+	transfer_from {
+		let owner = funded_account::<T>("owner", 0);
+		let kitty_id = create_kitties::<T>(&owner, 1);
+		let caller = funded_caller::<T>();
+		Kitties::<T>::set_approval_for_all(
+			RawOrigin::Signed(owner.clone()).into(),
+			lookup_of::<T>(&caller),
+			true,
+		)?;
+		let recipient = funded_account::<T>("recipient", 0);
+		create_kitties::<T>(&recipient, T::MaxKittiesOwned::get() - 1);
+	}: _(RawOrigin::Signed(caller), lookup_of::<T>(&owner), lookup_of::<T>(&recipient), kitty_id)
+	verify {
+		assert_eq!(Kitties::<T>::owner(kitty_id), Some(recipient));
+	}
+
+	burn {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Kitties::<T>::kitties(kitty_id).is_none());
+	}
+
+	// Breeding with an approved sire of another account, which pays the breeding fee
+	breed {
+		let caller = funded_caller::<T>();
+		let matron = create_kitties::<T>(&caller, 1);
+		let sire_owner = funded_account::<T>("sire_owner", 0);
+		let sire = create_kitties::<T>(&sire_owner, 1);
+		Kitties::<T>::approve_siring(
+			RawOrigin::Signed(sire_owner).into(),
+			sire,
+			lookup_of::<T>(&caller),
+		)?;
+		set_gender::<T>(matron, Gender::Female);
+		set_gender::<T>(sire, Gender::Male);
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 2);
+		let kitty_id = Kitties::<T>::next_kitty_id().unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), matron, sire)
+	verify {
+		assert_kitty_of::<T>(kitty_id, caller);
+	}
+
+	breed_with_sire {
+		let caller = funded_caller::<T>();
+		let matron = create_kitties::<T>(&caller, 1);
+		let sire_owner = funded_account::<T>("sire_owner", 0);
+		let sire = create_kitties::<T>(&sire_owner, 1);
+		Kitties::<T>::offer_sire(RawOrigin::Signed(sire_owner).into(), sire, 1u32.into())?;
+		set_gender::<T>(matron, Gender::Female);
+		set_gender::<T>(sire, Gender::Male);
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 2);
+		let kitty_id = Kitties::<T>::next_kitty_id().unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), matron, sire)
+	verify {
+		assert_kitty_of::<T>(kitty_id, caller);
+	}
+
+	approve_siring {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		let approved: T::AccountId = account("approved", 0, SEED);
+	}: _(RawOrigin::Signed(caller), kitty_id, lookup_of::<T>(&approved))
+	verify {
+		assert_eq!(Kitties::<T>::siring_approval(kitty_id), Some(approved));
+	}
+
+	cancel_siring_approval {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		let approved: T::AccountId = account("approved", 0, SEED);
+		Kitties::<T>::approve_siring(
+			RawOrigin::Signed(caller.clone()).into(),
+			kitty_id,
+			lookup_of::<T>(&approved),
+		)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert_eq!(Kitties::<T>::siring_approval(kitty_id), None);
+	}
+
+	offer_sire {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), kitty_id, 1u32.into())
+	verify {
+		assert!(Kitties::<T>::kitties(kitty_id).unwrap().sire_price.is_some());
+	}
+
+	// Updating the price of a kitty already on sale
+	sell {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		Kitties::<T>::sell(RawOrigin::Signed(caller.clone()).into(), kitty_id, 1u32.into())?;
+	}: _(RawOrigin::Signed(caller), kitty_id, 2u32.into())
+	verify {
+		assert_eq!(Kitties::<T>::kitties(kitty_id).unwrap().list_price, Some(2u32.into()));
+	}
+
+	cancel_sale {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		Kitties::<T>::sell(RawOrigin::Signed(caller.clone()).into(), kitty_id, 1u32.into())?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert_eq!(Kitties::<T>::kitties(kitty_id).unwrap().list_price, None);
+	}
+
+	// The buyer pays the price, the marketplace fee and reserves the deposit of one more kitty
+	// than they already own
+	buy {
+		let seller = funded_account::<T>("seller", 0);
+		let kitty_id = create_kitties::<T>(&seller, 1);
+		let price: BalanceOf<T> = 1_000_000u32.into();
+		Kitties::<T>::sell(RawOrigin::Signed(seller).into(), kitty_id, price)?;
+		let caller = funded_caller::<T>();
+		create_kitties::<T>(&caller, T::MaxKittiesOwned::get() - 1);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, price)
+	verify {
+		assert_eq!(Kitties::<T>::owner(kitty_id), Some(caller));
+	}
+
+	create_auction {
+		let duration = T::MaxAuctionDuration::get();
+		fill_auctions::<T>(duration);
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), kitty_id, 1u32.into(), duration)
+	verify {
+		assert!(Kitties::<T>::auctions(kitty_id).is_some());
+	}
+
+	// Outbidding someone, whose bid is released
+	bid {
+		let seller = funded_account::<T>("seller", 0);
+		let kitty_id = create_kitties::<T>(&seller, 1);
+		Kitties::<T>::create_auction(
+			RawOrigin::Signed(seller).into(),
+			kitty_id,
+			1u32.into(),
+			T::MaxAuctionDuration::get(),
+		)?;
+		let bidder = funded_account::<T>("bidder", 0);
+		Kitties::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, 1u32.into())?;
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, 2u32.into())
+	verify {
+		assert_eq!(Kitties::<T>::auctions(kitty_id).unwrap().best_bid, Some((caller, 2u32.into())));
+	}
+
+	cancel_auction {
+		let duration = T::MaxAuctionDuration::get();
+		fill_auctions::<T>(duration);
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		Kitties::<T>::create_auction(
+			RawOrigin::Signed(caller.clone()).into(),
+			kitty_id,
+			1u32.into(),
+			duration,
+		)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Kitties::<T>::auctions(kitty_id).is_none());
+	}
+
+	fractionalize {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, 1_000)
+	verify {
+		assert_eq!(Kitties::<T>::owner(kitty_id), Some(Kitties::<T>::account_id()));
+		assert_eq!(Kitties::<T>::shares(kitty_id, &caller), 1_000);
+	}
+
+	// The caller keeps some shares and the recipient already holds some
+	transfer_shares {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		Kitties::<T>::fractionalize(RawOrigin::Signed(caller.clone()).into(), kitty_id, 1_000)?;
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		Kitties::<T>::transfer_shares(
+			RawOrigin::Signed(caller.clone()).into(),
+			kitty_id,
+			lookup_of::<T>(&recipient),
+			1,
+		)?;
+	}: _(RawOrigin::Signed(caller), kitty_id, lookup_of::<T>(&recipient), 1)
+	verify {
+		assert_eq!(Kitties::<T>::shares(kitty_id, &recipient), 2);
+	}
+
+	// The redeemer bought all the shares and takes over the deposit of the issuer
+	redeem {
+		let issuer = funded_account::<T>("issuer", 0);
+		let kitty_id = create_kitties::<T>(&issuer, 1);
+		Kitties::<T>::fractionalize(RawOrigin::Signed(issuer.clone()).into(), kitty_id, 1_000)?;
+		let caller = funded_caller::<T>();
+		Kitties::<T>::transfer_shares(
+			RawOrigin::Signed(issuer).into(),
+			kitty_id,
+			lookup_of::<T>(&caller),
+			1_000,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert_eq!(Kitties::<T>::owner(kitty_id), Some(caller));
+		assert!(Kitties::<T>::fractions(kitty_id).is_none());
+	}
+
+	lease {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		let lessee: T::AccountId = account("lessee", 0, SEED);
+		let duration = T::MaxLeaseDuration::get();
+	}: _(RawOrigin::Signed(caller), kitty_id, lookup_of::<T>(&lessee), duration, 1u32.into())
+	verify {
+		assert!(Kitties::<T>::lease_offers(kitty_id).is_some());
+	}
+
+	cancel_lease_offer {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		let lessee: T::AccountId = account("lessee", 0, SEED);
+		Kitties::<T>::lease(
+			RawOrigin::Signed(caller.clone()).into(),
+			kitty_id,
+			lookup_of::<T>(&lessee),
+			T::MaxLeaseDuration::get(),
+			1u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Kitties::<T>::lease_offers(kitty_id).is_none());
+	}
+
+	// The lease ends in a block where all the other leases end
+	accept_lease {
+		let duration = T::MaxLeaseDuration::get();
+		create_leases::<T>(T::MaxLeasesPerBlock::get().saturating_sub(1), duration);
+		let owner = funded_account::<T>("owner", 0);
+		let kitty_id = create_kitties::<T>(&owner, 1);
+		let caller = funded_caller::<T>();
+		Kitties::<T>::lease(
+			RawOrigin::Signed(owner).into(),
+			kitty_id,
+			lookup_of::<T>(&caller),
+			duration,
+			1_000u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, 1_000u32.into())
+	verify {
+		assert_eq!(Kitties::<T>::breeder_of(kitty_id), Some(caller));
+	}
+
+	// The kitty was named by a previous owner, whose deposit is released
+	set_name {
+		let namer = funded_account::<T>("namer", 0);
+		let kitty_id = create_kitties::<T>(&namer, 1);
+		Kitties::<T>::set_name(RawOrigin::Signed(namer.clone()).into(), kitty_id, b"kitty".to_vec())?;
+		let caller = funded_caller::<T>();
+		Kitties::<T>::transfer(RawOrigin::Signed(namer).into(), lookup_of::<T>(&caller), kitty_id)?;
+		let name = sp_std::vec![b'k'; T::MaxNameLength::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, name.clone())
+	verify {
+		assert_eq!(Kitties::<T>::kitty_name(kitty_id).map(|n| n.name.to_vec()), Some(name));
+	}
+
+	clear_name {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		Kitties::<T>::set_name(RawOrigin::Signed(caller.clone()).into(), kitty_id, b"kitty".to_vec())?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Kitties::<T>::kitty_name(kitty_id).is_none());
+	}
+
+	force_clear_name {
+		let owner = funded_account::<T>("owner", 0);
+		let kitty_id = create_kitties::<T>(&owner, 1);
+		Kitties::<T>::set_name(RawOrigin::Signed(owner).into(), kitty_id, b"kitty".to_vec())?;
+		let origin = T::ForceOrigin::successful_origin();
+	}: _<T::Origin>(origin, kitty_id)
+	verify {
+		assert!(Kitties::<T>::kitty_name(kitty_id).is_none());
+	}
+
+	set_metadata {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitties::<T>(&caller, 1);
+		let metadata = sp_std::vec![b'm'; T::MaxMetadataLength::get() as usize];
+	}: _(RawOrigin::Signed(caller), kitty_id, metadata)
+	verify {
+		assert!(Kitties::<T>::kitty_metadata(kitty_id).is_some());
+	}
+
+	// Revealing `r` bred kitties
+	reveal_kitties {
+		let r in 0 .. T::MaxRevealsPerBlock::get();
+		let reveal_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		for i in 0..r {
+			let owner = funded_account::<T>("owner", i);
+			let kitty_id = Kitties::<T>::next_kitty_id().unwrap();
+			KittiesCount::<T>::put(kitty_id);
+			PendingKitties::<T>::insert(kitty_id, PendingKitty {
+				owner,
+				parents: None,
+				genes: Some(([0u8; 16], [u8::MAX; 16])),
+				generation: 1,
+				reveal_at,
+			});
+			RevealsAt::<T>::append(reveal_at, kitty_id);
+		}
+	}: {
+		Kitties::<T>::on_initialize(reveal_at);
+	}
+	verify {
+		assert!(Kitties::<T>::reveals_at(reveal_at).is_empty());
+		assert_eq!(PendingKitties::<T>::iter().count(), 0);
+	}
+
+	// Ending `l` leases
+	on_initialize {
+		let l in 0 .. T::MaxLeasesPerBlock::get();
+		let duration = T::MaxLeaseDuration::get();
+		create_leases::<T>(l, duration);
+		let end = frame_system::Pallet::<T>::block_number() + duration;
+	}: {
+		Kitties::<T>::on_initialize(end);
+	}
+	verify {
+		assert!(Kitties::<T>::leases_ending_at(end).is_empty());
+		assert_eq!(Leases::<T>::iter().count(), 0);
+	}
+
+	// Settling `a` auctions that all received a bid
+	on_finalize {
+		let a in 0 .. T::MaxAuctionsPerBlock::get();
+
+		let duration: T::BlockNumber = 1u32.into();
+		let end = frame_system::Pallet::<T>::block_number() + duration;
+		for i in 0..a {
+			let seller = funded_account::<T>("seller", i);
+			let kitty_id = create_kitties::<T>(&seller, 1);
+			Kitties::<T>::create_auction(
+				RawOrigin::Signed(seller).into(),
+				kitty_id,
+				1u32.into(),
+				duration,
+			)?;
+			let bidder = funded_account::<T>("bidder", i);
+			Kitties::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, 1u32.into())?;
+		}
+	}: {
+		Kitties::<T>::on_finalize(end);
+	}
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(Kitties::<T>::auctions_ending_at(end).is_empty());
+		assert_eq!(Auctions::<T>::iter().count(), 0);
 	}
 }
 
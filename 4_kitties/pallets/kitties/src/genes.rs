@@ -0,0 +1,61 @@
+//! Decoding of the visible traits of a kitty from its DNA.
+//!
+//! Byte 0 picks the body, byte 1 the eyes, byte 2 the accessory, byte 3 the fur pattern and
+//! byte 4 the mouth. The front-end draws the avatar from `KittiesApi::kitty_traits`, so
+//! `KittyAvatar.js` only needs one image per variant of each part.
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Gender {
+	Female,
+	Male,
+}
+
+/// The traits of a kitty, as decoded from its DNA.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct KittyTraits {
+	/// One of `BODY_COLORS` variants.
+	pub body_color: u8,
+	/// One of `EYE_SHAPES` variants.
+	pub eye_shape: u8,
+	/// One of `ACCESSORIES` variants.
+	pub accessory: u8,
+	/// One of `PATTERNS` variants.
+	pub pattern: u8,
+	/// One of `MOUTHS` variants.
+	pub mouth: u8,
+	/// From 0 to 16, every point being half as likely as the previous one.
+	pub rarity_score: u8,
+	pub gender: Gender,
+}
+
+impl KittyTraits {
+	pub const BODY_COLORS: u8 = 15;
+	pub const EYE_SHAPES: u8 = 15;
+	pub const ACCESSORIES: u8 = 20;
+	pub const PATTERNS: u8 = 10;
+	pub const MOUTHS: u8 = 10;
+
+	pub fn from_dna(dna: &[u8; 16]) -> Self {
+		// Leading zero bits are increasingly unlikely in random DNA
+		let rarity_score = u16::from_be_bytes([dna[5], dna[6]]).leading_zeros() as u8;
+		let gender = if dna[15] % 2 == 0 { Gender::Female } else { Gender::Male };
+
+		KittyTraits {
+			body_color: dna[0] % Self::BODY_COLORS,
+			eye_shape: dna[1] % Self::EYE_SHAPES,
+			accessory: dna[2] % Self::ACCESSORIES,
+			pattern: dna[3] % Self::PATTERNS,
+			mouth: dna[4] % Self::MOUTHS,
+			rarity_score,
+			gender,
+		}
+	}
+
+	/// Whether two kitties with these traits can breed together.
+	pub fn can_breed_with(&self, other: &KittyTraits) -> bool {
+		self.gender != other.gender
+	}
+}
@@ -1,6 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+pub use genes::{Gender, KittyTraits};
 pub use pallet::*;
 
+pub mod genes;
+pub mod migrations;
+pub mod nonfungibles;
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[cfg(test)]
 mod mock;
 
@@ -10,23 +19,141 @@ mod tests;
 #[frame_support::pallet]
 pub mod pallet {
 
+	use crate::genes::KittyTraits;
+	pub use crate::weights::WeightInfo;
 	use codec::{Codec, Decode, Encode};
 	use frame_support::{
 		dispatch::DispatchResult,
 		pallet_prelude::*,
-		traits::{Currency, ExistenceRequirement, Randomness, ReservableCurrency},
+		traits::{Currency, ExistenceRequirement, Randomness, ReservableCurrency, StorageVersion},
+		transactional, PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 	use sp_io::hashing::blake2_128;
-	use sp_runtime::traits::{AtLeast32BitUnsigned, Bounded, Saturating, StaticLookup};
+	use sp_runtime::{
+		traits::{
+			AccountIdConversion, AtLeast32BitUnsigned, Bounded, Saturating, StaticLookup, Zero,
+		},
+		Perbill, RuntimeDebug,
+	};
+	use sp_std::{convert::TryFrom, vec::Vec};
 
-	type BalanceOf<T> =
+	pub(crate) type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	/// How deep `family_tree` goes into the ancestors of a kitty.
+	pub const MAX_FAMILY_TREE_DEPTH: u32 = 8;
+
+	/// The current storage version, 0 being the layout of the `5_kitties_app` pallet.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[derive(Encode, Decode)]
 	pub struct Kitty<T: Config> {
 		pub dna: [u8; 16],
 		pub list_price: Option<BalanceOf<T>>,
+		/// Price asked by the owner for breeding with this kitty as the sire.
+		pub sire_price: Option<BalanceOf<T>>,
+		/// The kitties this one was bred from, `None` for kitties created from scratch.
+		pub parents: Option<(T::KittyIndex, T::KittyIndex)>,
+		/// 0 for created kitties, one more than the older parent for bred ones.
+		pub generation: u32,
+		pub birth_block: T::BlockNumber,
+	}
+
+	/// An English auction of a kitty.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct Auction<AccountId, Balance, BlockNumber> {
+		pub seller: AccountId,
+		/// Bids below this price are refused.
+		pub reserve_price: Balance,
+		/// The auction is settled at the end of this block.
+		pub end: BlockNumber,
+		/// The highest bidder and the amount reserved from them.
+		pub best_bid: Option<(AccountId, Balance)>,
+	}
+
+	type AuctionOf<T> = Auction<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Terms offered by the owner of a kitty to lease it to `lessee`.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct LeaseOffer<AccountId, Balance, BlockNumber> {
+		pub lessee: AccountId,
+		/// Number of blocks the lease lasts once accepted.
+		pub duration: BlockNumber,
+		/// Paid by the lessee to the owner when accepting the offer.
+		pub price: Balance,
+	}
+
+	/// The breeding rights of a kitty, held by `lessee` until the start of block `end`.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct Lease<AccountId, BlockNumber> {
+		pub lessee: AccountId,
+		pub end: BlockNumber,
+	}
+
+	type LeaseOfferOf<T> = LeaseOffer<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Amount of shares of a fractionalized kitty.
+	pub type Shares = u64;
+
+	/// A kitty locked in the pallet account against fungible shares.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct Fraction<AccountId> {
+		/// The owner who locked the kitty, whose deposit stays reserved until it is redeemed.
+		pub issuer: AccountId,
+		pub total_shares: Shares,
+	}
+
+	/// The unique name of a kitty and the deposit reserved for it.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct KittyName<AccountId, Balance, Name> {
+		pub name: Name,
+		/// The owner who named the kitty, the deposit stays reserved from them until the kitty
+		/// is renamed, its name is cleared or it is burned.
+		pub depositor: AccountId,
+		pub deposit: Balance,
+	}
+
+	pub(crate) type NameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+
+	type KittyNameOf<T> =
+		KittyName<<T as frame_system::Config>::AccountId, BalanceOf<T>, NameOf<T>>;
+
+	/// A created or bred kitty whose DNA is drawn at the start of block `reveal_at`, from
+	/// randomness that was unknown when it was committed.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct PendingKitty<AccountId, KittyIndex, BlockNumber> {
+		pub owner: AccountId,
+		/// The parents of bred kitties and their DNA, the parents may be gone by the reveal.
+		pub parents: Option<(KittyIndex, KittyIndex)>,
+		pub genes: Option<([u8; 16], [u8; 16])>,
+		pub generation: u32,
+		pub reveal_at: BlockNumber,
+	}
+
+	type PendingKittyOf<T> = PendingKitty<
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::KittyIndex,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// A kitty and its known ancestors.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct FamilyTree<KittyIndex, BlockNumber> {
+		pub kitty_id: KittyIndex,
+		pub dna: [u8; 16],
+		pub generation: u32,
+		pub birth_block: BlockNumber,
+		/// The trees of both parents, empty for created kitties or beyond the requested depth.
+		pub parents: Vec<FamilyTree<KittyIndex, BlockNumber>>,
 	}
 
 	#[pallet::config]
@@ -48,11 +175,71 @@ pub mod pallet {
 			+ Copy
 			+ MaybeSerializeDeserialize;
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+		/// Deposit reserved from the owner of every kitty, 0 to not require any.
 		type ReserveAmount: Get<BalanceOf<Self>>;
+		/// Whether siblings, or a parent and its child, may be bred together.
+		#[pallet::constant]
+		type AllowInbreeding: Get<bool>;
+		/// Number of blocks both parents must rest after breeding.
+		#[pallet::constant]
+		type BreedingCooldown: Get<Self::BlockNumber>;
+		/// Paid by the breeder to the owner of the sire when breeding with someone else's kitty.
+		#[pallet::constant]
+		type BreedingFee: Get<BalanceOf<Self>>;
+		/// The longest an auction can run, in blocks.
+		#[pallet::constant]
+		type MaxAuctionDuration: Get<Self::BlockNumber>;
+		/// The maximum number of auctions ending in the same block.
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+		/// Whether kitties can be sold, auctioned or offered as sires. Existing listings can still
+		/// be withdrawn when disabled.
+		#[pallet::constant]
+		type MarketplaceEnabled: Get<bool>;
+		/// Share of every sale price taken by the marketplace.
+		#[pallet::constant]
+		type MarketplaceFee: Get<Perbill>;
+		/// The account receiving the marketplace fees.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+		/// The maximum number of kitties a single account can own.
+		#[pallet::constant]
+		type MaxKittiesOwned: Get<u32>;
+		/// The longest a kitty can be leased, in blocks.
+		#[pallet::constant]
+		type MaxLeaseDuration: Get<Self::BlockNumber>;
+		/// The maximum number of leases ending in the same block.
+		#[pallet::constant]
+		type MaxLeasesPerBlock: Get<u32>;
+		/// The pallet id, used to derive the account holding fractionalized kitties.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// The amount reserved from the owner naming a kitty.
+		#[pallet::constant]
+		type NameDeposit: Get<BalanceOf<Self>>;
+		/// The longest name of a kitty, in bytes.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		/// The longest metadata URI of a kitty, in bytes.
+		#[pallet::constant]
+		type MaxMetadataLength: Get<u32>;
+		/// The origin allowed to clear offensive names.
+		type ForceOrigin: EnsureOrigin<Self::Origin>;
+		/// Number of blocks between the creation or breeding of a kitty and the draw of its DNA,
+		/// so that neither the caller nor the block producer can predict it. 0 draws it at once,
+		/// from randomness known to block producers, which only suits development chains.
+		#[pallet::constant]
+		type RevealDelay: Get<Self::BlockNumber>;
+		/// The maximum number of kitties revealed in the same block.
+		#[pallet::constant]
+		type MaxRevealsPerBlock: Get<u32>;
+		/// Information on runtime weights.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -69,6 +256,166 @@ pub mod pallet {
 	pub type Owner<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::KittyIndex, Option<T::AccountId>, ValueQuery>;
 
+	/// Kitties owned by each account, so they can be listed without scanning `Owner`.
+	#[pallet::storage]
+	#[pallet::getter(fn owned_kitties)]
+	pub type OwnedKitties<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::KittyIndex,
+		(),
+		OptionQuery,
+	>;
+
+	/// Number of kitties owned by each account, counting the kitties still pending their DNA.
+	#[pallet::storage]
+	#[pallet::getter(fn owned_kitties_count)]
+	pub type OwnedKittiesCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The account allowed to transfer a kitty on behalf of its owner.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_approval)]
+	pub type KittyApprovals<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, T::AccountId, OptionQuery>;
+
+	/// Operators allowed to transfer and approve all the kitties of an owner.
+	#[pallet::storage]
+	#[pallet::getter(fn operator_approval)]
+	pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// First block in which a kitty can breed again.
+	#[pallet::storage]
+	#[pallet::getter(fn breeding_ready_at)]
+	pub type BreedingReadyAt<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, T::BlockNumber, ValueQuery>;
+
+	/// The account the owner of a kitty allowed to use it as a sire.
+	#[pallet::storage]
+	#[pallet::getter(fn siring_approval)]
+	pub type SiringApprovals<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, T::AccountId, OptionQuery>;
+
+	/// Running auctions.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, T::KittyIndex, AuctionOf<T>>;
+
+	/// The auctions to settle at the end of each block.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_ending_at)]
+	pub type AuctionsEndingAt<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<T::KittyIndex>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn lease_offers)]
+	pub type LeaseOffers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, LeaseOfferOf<T>, OptionQuery>;
+
+	/// Kitties whose breeding rights are leased to another account than their owner.
+	#[pallet::storage]
+	#[pallet::getter(fn leases)]
+	pub type Leases<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::KittyIndex,
+		Lease<T::AccountId, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// Kitties whose lease expires at the start of each block.
+	#[pallet::storage]
+	#[pallet::getter(fn leases_ending_at)]
+	pub type LeasesEndingAt<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<T::KittyIndex>, ValueQuery>;
+
+	/// Kitties locked against shares, see `fractionalize`.
+	#[pallet::storage]
+	#[pallet::getter(fn fractions)]
+	pub type Fractions<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, Fraction<T::AccountId>, OptionQuery>;
+
+	/// The shares of fractionalized kitties held by each account.
+	#[pallet::storage]
+	#[pallet::getter(fn shares)]
+	pub type KittyShares<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::KittyIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		Shares,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_name)]
+	pub type KittyNames<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, KittyNameOf<T>, OptionQuery>;
+
+	/// The kitty holding each name, names are unique.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_by_name)]
+	pub type NamedKitties<T: Config> =
+		StorageMap<_, Blake2_128Concat, NameOf<T>, T::KittyIndex, OptionQuery>;
+
+	/// A URI describing the kitty, set by its owner.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_metadata)]
+	pub type KittyMetadata<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::KittyIndex,
+		BoundedVec<u8, T::MaxMetadataLength>,
+		OptionQuery,
+	>;
+
+	/// Kitties waiting for their DNA, see `RevealDelay`.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_kitties)]
+	pub type PendingKitties<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, PendingKittyOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reveals_at)]
+	pub type RevealsAt<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<T::KittyIndex>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Kitties minted at genesis as `(owner, dna)`, indexed in order from 1. The deposit is
+		/// reserved from each owner, who must be endowed by the balances genesis.
+		pub kitties: Vec<(T::AccountId, [u8; 16])>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { kitties: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (owner, dna) in &self.kitties {
+				let kitty_id = Pallet::<T>::next_kitty_id().expect("too many genesis kitties");
+				Pallet::<T>::kitty_create(owner.clone(), kitty_id, *dna, None, 0)
+					.expect("genesis kitty owners can afford the deposit and own few kitties");
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::metadata(T::AccountId = "AccountId")]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -76,6 +423,55 @@ pub mod pallet {
 		KittyCreated(T::AccountId, T::KittyIndex),
 		KittyTransferred(T::AccountId, T::AccountId, T::KittyIndex),
 		KittyOnSale(T::AccountId, T::KittyIndex, BalanceOf<T>),
+		/// The price of a kitty already on sale changed. [owner, kitty_id, old_price, new_price]
+		KittyPriceUpdated(T::AccountId, T::KittyIndex, BalanceOf<T>, BalanceOf<T>),
+		/// A kitty was taken off sale by its owner. [owner, kitty_id]
+		KittyDelisted(T::AccountId, T::KittyIndex),
+		/// A kitty was bought at its list price. [seller, buyer, kitty_id, price]
+		KittySold(T::AccountId, T::AccountId, T::KittyIndex, BalanceOf<T>),
+		/// The owner allowed an account to breed with a kitty. [owner, kitty_id, approved]
+		SiringApproved(T::AccountId, T::KittyIndex, T::AccountId),
+		/// The owner withdrew the siring approval of a kitty. [owner, kitty_id]
+		SiringApprovalCancelled(T::AccountId, T::KittyIndex),
+		/// The approved account of a kitty changed. [owner, approved, kitty_id]
+		Approval(T::AccountId, Option<T::AccountId>, T::KittyIndex),
+		/// An operator was (dis)allowed for all kitties of an owner. [owner, operator, approved]
+		ApprovalForAll(T::AccountId, T::AccountId, bool),
+		/// A kitty was burned by its owner and the deposit returned. [owner, kitty_id]
+		KittyBurned(T::AccountId, T::KittyIndex),
+		/// A kitty is offered as a sire. [owner, kitty_id, price]
+		SireOffered(T::AccountId, T::KittyIndex, BalanceOf<T>),
+		/// A kitty is put up for auction. [seller, kitty_id, reserve_price, end]
+		AuctionCreated(T::AccountId, T::KittyIndex, BalanceOf<T>, T::BlockNumber),
+		/// A new highest bid. [bidder, kitty_id, amount]
+		BidPlaced(T::AccountId, T::KittyIndex, BalanceOf<T>),
+		/// The kitty went to the highest bidder. [seller, bidder, kitty_id, amount]
+		AuctionSettled(T::AccountId, T::AccountId, T::KittyIndex, BalanceOf<T>),
+		/// The auction was cancelled, ended without bids or could not be settled. [kitty_id]
+		AuctionCancelled(T::KittyIndex),
+		/// A kitty was locked and shares minted to its owner. [owner, kitty_id, total_shares]
+		KittyFractionalized(T::AccountId, T::KittyIndex, Shares),
+		/// Shares of a kitty changed hands. [from, to, kitty_id, amount]
+		SharesTransferred(T::AccountId, T::AccountId, T::KittyIndex, Shares),
+		/// All the shares of a kitty were redeemed for the kitty itself. [redeemer, kitty_id]
+		KittyRedeemed(T::AccountId, T::KittyIndex),
+		/// A kitty is offered for lease. [owner, kitty_id, lessee, duration, price]
+		LeaseOffered(T::AccountId, T::KittyIndex, T::AccountId, T::BlockNumber, BalanceOf<T>),
+		/// The owner withdrew a lease offer. [owner, kitty_id]
+		LeaseOfferCancelled(T::AccountId, T::KittyIndex),
+		/// The lessee accepted a lease offer. [owner, kitty_id, lessee, end]
+		LeaseStarted(T::AccountId, T::KittyIndex, T::AccountId, T::BlockNumber),
+		/// The breeding rights of a kitty went back to its owner. [lessee, kitty_id]
+		LeaseEnded(T::AccountId, T::KittyIndex),
+		/// An owner named a kitty. [owner, kitty_id, name]
+		KittyNamed(T::AccountId, T::KittyIndex, Vec<u8>),
+		/// The name of a kitty was cleared by its owner or the force origin. [kitty_id]
+		KittyNameCleared(T::KittyIndex),
+		/// An owner set or cleared the metadata of a kitty. [owner, kitty_id]
+		KittyMetadataSet(T::AccountId, T::KittyIndex),
+		/// A kitty was created or bred, it is revealed at the start of block `reveal_at`.
+		/// [owner, kitty_id, reveal_at]
+		KittyPending(T::AccountId, T::KittyIndex, T::BlockNumber),
 	}
 
 	#[pallet::error]
@@ -89,24 +485,76 @@ pub mod pallet {
 		KittyNotForSale,
 		InsufficientReserveBalance,
 		InsufficientPaymentBalance,
+		ExceedMaxKittiesOwned,
+		InbreedingNotAllowed,
+		KittyOnCooldown,
+		SiringNotApproved,
+		SireNotOffered,
+		NotApproved,
+		IncompatibleGender,
+		PriceChanged,
+		KittyInAuction,
+		AuctionNotFound,
+		AuctionEnded,
+		AuctionHasBids,
+		InvalidAuctionDuration,
+		TooManyAuctions,
+		BidTooLow,
+		MarketplaceDisabled,
+		NotFractionalized,
+		KittyFractionalized,
+		InvalidShareAmount,
+		InsufficientShares,
+		KittyLeased,
+		LeaseNotOffered,
+		InvalidLeaseDuration,
+		TooManyLeases,
+		InvalidName,
+		NameTooLong,
+		NameTaken,
+		NameNotSet,
+		MetadataTooLong,
+		TooManyReveals,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let revealed = Self::reveal_kitties(n);
+			let expired = Self::expire_leases(n);
+
+			// Account for the settlements done in `on_finalize`
+			let ending = AuctionsEndingAt::<T>::decode_len(n).unwrap_or(0) as u32;
+			T::DbWeight::get()
+				.reads(1)
+				.saturating_add(T::WeightInfo::reveal_kitties(revealed))
+				.saturating_add(T::WeightInfo::on_initialize(expired))
+				.saturating_add(T::WeightInfo::on_finalize(ending))
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			for kitty_id in AuctionsEndingAt::<T>::take(n) {
+				if let Some(auction) = Auctions::<T>::take(kitty_id) {
+					Self::settle_auction(kitty_id, auction);
+				}
+			}
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::create())]
 		pub fn create(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
 			let kitty_id = Self::next_kitty_id()?;
 
-			let dna = Self::random_value(&who);
-
-			Self::kitty_create(who, kitty_id, dna)?;
+			Self::new_kitty(who, kitty_id, None, None, 0)?;
 
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::transfer())]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			recipient: <T::Lookup as StaticLookup>::Source,
@@ -115,12 +563,111 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 			let recipient = T::Lookup::lookup(recipient)?;
 
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+
 			Self::kitty_transfer(who.clone(), recipient.clone(), kitty_id)?;
 
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		/// Allow `approved` to transfer the kitty with `transfer_from`, or clear the approval with
+		/// `None`. Callable by the owner or one of its operators. The approval is cleared when the
+		/// kitty changes hands.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			approved: Option<<T::Lookup as StaticLookup>::Source>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let approved = approved.map(T::Lookup::lookup).transpose()?;
+
+			let owner = Owner::<T>::get(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+			ensure!(
+				who == owner || OperatorApprovals::<T>::contains_key(&owner, &who),
+				Error::<T>::NotApproved
+			);
+
+			match &approved {
+				Some(account) => KittyApprovals::<T>::insert(kitty_id, account),
+				None => KittyApprovals::<T>::remove(kitty_id),
+			}
+
+			Self::deposit_event(Event::Approval(owner, approved, kitty_id));
+
+			Ok(())
+		}
+
+		/// Allow or disallow `operator` to transfer and approve all the kitties of the caller.
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: <T::Lookup as StaticLookup>::Source,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+
+			if approved {
+				OperatorApprovals::<T>::insert(&who, &operator, ());
+			} else {
+				OperatorApprovals::<T>::remove(&who, &operator);
+			}
+
+			Self::deposit_event(Event::ApprovalForAll(who, operator, approved));
+
+			Ok(())
+		}
+
+		/// Transfer a kitty of `from` to `to`. The caller must be the owner, the approved account
+		/// of the kitty, or an operator of the owner.
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+			kitty_id: T::KittyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+
+			ensure!(
+				who == from ||
+					Self::kitty_approval(kitty_id) == Some(who.clone()) ||
+					OperatorApprovals::<T>::contains_key(&from, &who),
+				Error::<T>::NotApproved
+			);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+
+			Self::kitty_transfer(from, to, kitty_id)?;
+
+			Ok(())
+		}
+
+		/// Burn a kitty and release the amount reserved when it was created or received.
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			// Ensure kitty_id is valid
+			let _ = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+
+			ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+
+			Self::kitty_burn(who, kitty_id);
+
+			Ok(())
+		}
+
+		/// Breed a new kitty from `kitty_id_1`, which must belong to the caller, and the sire
+		/// `kitty_id_2`. The sire must also belong to the caller, or its owner must have approved
+		/// the caller with `approve_siring`, in which case the sire owner is paid `BreedingFee`.
+		/// Leased kitties are bred by their lessee instead of their owner.
+		#[pallet::weight(T::WeightInfo::breed())]
+		#[transactional]
 		pub fn breed(
 			origin: OriginFor<T>,
 			kitty_id_1: T::KittyIndex,
@@ -133,21 +680,131 @@ pub mod pallet {
 			let kitty_1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyIndex)?;
 			let kitty_2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyIndex)?;
 
-			let kitty_id = Self::next_kitty_id()?;
+			ensure!(Some(who.clone()) == Self::breeder_of(kitty_id_1), Error::<T>::NotOwner);
 
-			let selector = Self::random_value(&who);
-			let mut new_dna = [0u8; 16];
+			let sire_owner = Self::breeder_of(kitty_id_2).ok_or(Error::<T>::KittyWithoutOwner)?;
+			let fee = if sire_owner != who {
+				ensure!(
+					Self::siring_approval(kitty_id_2) == Some(who.clone()),
+					Error::<T>::SiringNotApproved
+				);
+				// The approval is used up by a single breeding
+				SiringApprovals::<T>::remove(kitty_id_2);
+				Some((&sire_owner, T::BreedingFee::get()))
+			} else {
+				None
+			};
 
-			for i in 0..new_dna.len() {
-				new_dna[i] = (selector[i] & kitty_1.dna[i]) | (!selector[i] & kitty_2.dna[i]);
-			}
+			Self::do_breed(&who, kitty_id_1, &kitty_1, kitty_id_2, &kitty_2, fee)?;
+
+			Ok(())
+		}
+
+		/// Breed the caller's `my_kitty` with `sire_id`, a kitty of another account offered with
+		/// `offer_sire`. The sire owner is paid the asked price and keeps the sire, the offspring
+		/// belongs to the caller.
+		#[pallet::weight(T::WeightInfo::breed_with_sire())]
+		#[transactional]
+		pub fn breed_with_sire(
+			origin: OriginFor<T>,
+			my_kitty: T::KittyIndex,
+			sire_id: T::KittyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(T::MarketplaceEnabled::get(), Error::<T>::MarketplaceDisabled);
+
+			ensure!(my_kitty != sire_id, Error::<T>::SameParentIndex);
+
+			let matron = Self::kitties(my_kitty).ok_or(Error::<T>::InvalidKittyIndex)?;
+			let sire = Self::kitties(sire_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+
+			ensure!(Some(who.clone()) == Self::breeder_of(my_kitty), Error::<T>::NotOwner);
+
+			let sire_owner = Self::breeder_of(sire_id).ok_or(Error::<T>::KittyWithoutOwner)?;
+
+			// Owners breed their own kitties with `breed`
+			ensure!(who != sire_owner, Error::<T>::KittyAlreadyOwned);
+
+			let sire_price = sire.sire_price.ok_or(Error::<T>::SireNotOffered)?;
+
+			Self::do_breed(
+				&who,
+				my_kitty,
+				&matron,
+				sire_id,
+				&sire,
+				Some((&sire_owner, sire_price)),
+			)?;
+
+			Ok(())
+		}
+
+		/// Allow `approved` to use the kitty as a sire once.
+		#[pallet::weight(T::WeightInfo::approve_siring())]
+		pub fn approve_siring(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			approved: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let approved = T::Lookup::lookup(approved)?;
+
+			ensure!(Some(who.clone()) == Self::breeder_of(kitty_id), Error::<T>::NotOwner);
+
+			SiringApprovals::<T>::insert(kitty_id, approved.clone());
+
+			Self::deposit_event(Event::SiringApproved(who, kitty_id, approved));
 
-			Self::kitty_create(who, kitty_id, new_dna)?;
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::cancel_siring_approval())]
+		pub fn cancel_siring_approval(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Some(who.clone()) == Self::breeder_of(kitty_id), Error::<T>::NotOwner);
+			ensure!(SiringApprovals::<T>::contains_key(kitty_id), Error::<T>::SiringNotApproved);
+
+			SiringApprovals::<T>::remove(kitty_id);
+
+			Self::deposit_event(Event::SiringApprovalCancelled(who, kitty_id));
+
+			Ok(())
+		}
+
+		/// Offer the kitty as a sire to other accounts for `price` per breeding.
+		#[pallet::weight(T::WeightInfo::offer_sire())]
+		pub fn offer_sire(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(T::MarketplaceEnabled::get(), Error::<T>::MarketplaceDisabled);
+
+			// Ensure kitty_id is valid
+			let _ = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+
+			ensure!(Some(who.clone()) == Self::breeder_of(kitty_id), Error::<T>::NotOwner);
+
+			// Update the sire price
+			Kitties::<T>::mutate(kitty_id, |kitty| {
+				if let Some(k) = kitty {
+					k.sire_price = Some(price);
+				}
+			});
+
+			Self::deposit_event(Event::SireOffered(who, kitty_id, price));
 
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::sell())]
 		pub fn sell(
 			origin: OriginFor<T>,
 			kitty_id: T::KittyIndex,
@@ -155,27 +812,64 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			ensure!(T::MarketplaceEnabled::get(), Error::<T>::MarketplaceDisabled);
+
 			// Ensure kitty_id is valid
 			let _ = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
 
 			ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
 
 			// Update the list price
+			let old_price = Kitties::<T>::mutate(kitty_id, |kitty| {
+				kitty.as_mut().and_then(|k| k.list_price.replace(list_price))
+			});
+
+			match old_price {
+				Some(old_price) => Self::deposit_event(Event::KittyPriceUpdated(
+					who, kitty_id, old_price, list_price,
+				)),
+				None => Self::deposit_event(Event::KittyOnSale(who, kitty_id, list_price)),
+			}
+
+			Ok(())
+		}
+
+		/// Take the kitty off sale.
+		#[pallet::weight(T::WeightInfo::cancel_sale())]
+		pub fn cancel_sale(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+
+			ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+			ensure!(kitty.list_price.is_some(), Error::<T>::KittyNotForSale);
+
 			Kitties::<T>::mutate(kitty_id, |kitty| {
 				if let Some(k) = kitty {
-					k.list_price = Some(list_price);
+					k.list_price = None;
 				}
 			});
 
-			Self::deposit_event(Event::KittyOnSale(who, kitty_id, list_price));
+			Self::deposit_event(Event::KittyDelisted(who, kitty_id));
 
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
-		pub fn buy(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+		/// Buy a kitty at its list price, as long as it does not exceed `max_price`. The
+		/// marketplace fee is taken from the price and sent to the treasury account.
+		#[pallet::weight(T::WeightInfo::buy())]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			ensure!(T::MarketplaceEnabled::get(), Error::<T>::MarketplaceDisabled);
+
 			// Ensure kitty_id is valid
 			let kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
 
@@ -188,85 +882,736 @@ pub mod pallet {
 			// Get the kitty list price only if it's for sale
 			let kitty_price = kitty.list_price.ok_or(Error::<T>::KittyNotForSale)?;
 
+			// The seller may have raised the price after the buyer sent the transaction
+			ensure!(kitty_price <= max_price, Error::<T>::PriceChanged);
+
 			// Ensure there is sufficient balance of buyer to pay the price and reserve the amount of money for kitty
 			ensure!(
 				T::Currency::free_balance(&who) >= kitty_price + T::ReserveAmount::get(),
 				Error::<T>::InsufficientPaymentBalance
 			);
 
-			// This should succeed
-			T::Currency::transfer(
-				&who,
-				&original_owner,
-				kitty_price,
-				ExistenceRequirement::AllowDeath,
-			)?;
+			Self::complete_sale(&original_owner, &who, kitty_id, kitty_price)?;
 
-			// This should succeed
-			Self::kitty_transfer(original_owner, who, kitty_id)?;
+			Self::deposit_event(Event::KittySold(original_owner, who, kitty_id, kitty_price));
 
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		fn random_value(sender: &T::AccountId) -> [u8; 16] {
-			let payload = (
-				T::Randomness::random_seed(),
-				&sender,
-				<frame_system::Pallet<T>>::extrinsic_index(),
+		/// Put the kitty up for an English auction ending `duration` blocks from now. Any fixed
+		/// price listing is withdrawn.
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			reserve_price: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(T::MarketplaceEnabled::get(), Error::<T>::MarketplaceDisabled);
+
+			// Ensure kitty_id is valid
+			let _ = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+
+			ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+			ensure!(
+				!duration.is_zero() && duration <= T::MaxAuctionDuration::get(),
+				Error::<T>::InvalidAuctionDuration
 			);
-			payload.using_encoded(blake2_128)
-		}
 
-		// Calculate the next kitty index
-		fn next_kitty_id() -> Result<T::KittyIndex, Error<T>> {
-			match Self::kitties_count() {
-				Some(id) => {
-					// Ensure id won't overflow
-					ensure!(id < T::KittyIndex::max_value(), Error::<T>::KittiesCountOverflow);
-					Ok(id.saturating_add(T::KittyIndex::from(1u8)))
+			let end = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+			AuctionsEndingAt::<T>::try_mutate(end, |ending| -> DispatchResult {
+				ensure!(
+					(ending.len() as u32) < T::MaxAuctionsPerBlock::get(),
+					Error::<T>::TooManyAuctions
+				);
+				ending.push(kitty_id);
+				Ok(())
+			})?;
+
+			Kitties::<T>::mutate(kitty_id, |kitty| {
+				if let Some(k) = kitty {
+					k.list_price = None;
 				}
-				// Start count from 1
-				None => Ok(T::KittyIndex::min_value().saturating_add(T::KittyIndex::from(1u8))),
-			}
-		}
+			});
+			Auctions::<T>::insert(
+				kitty_id,
+				Auction { seller: who.clone(), reserve_price, end, best_bid: None },
+			);
 
-		// Setter of kitty count for testing
-		#[cfg(test)]
-		pub fn set_kitty_count(kitty_count: T::KittyIndex) {
-			KittiesCount::<T>::put(kitty_count);
+			Self::deposit_event(Event::AuctionCreated(who, kitty_id, reserve_price, end));
+
+			Ok(())
 		}
 
-		// Setter of kitty owner for testing
-		#[cfg(test)]
-		pub fn set_kitty_owner(kitty_index: T::KittyIndex, owner: Option<T::AccountId>) {
-			Owner::<T>::mutate(kitty_index, |v| {
+		/// Bid `amount` on an auction. The amount and the deposit of the kitty are reserved until
+		/// the bidder is outbid or the auction is settled.
+		#[pallet::weight(T::WeightInfo::bid())]
+		pub fn bid(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(T::MarketplaceEnabled::get(), Error::<T>::MarketplaceDisabled);
+
+			// The best bidder must be able to take the kitty when the auction is settled
+			ensure!(
+				Self::owned_kitties_count(&who) < T::MaxKittiesOwned::get(),
+				Error::<T>::ExceedMaxKittiesOwned
+			);
+			let deposit = T::ReserveAmount::get();
+
+			Auctions::<T>::try_mutate(kitty_id, |maybe_auction| -> DispatchResult {
+				let auction = maybe_auction.as_mut().ok_or(Error::<T>::AuctionNotFound)?;
+
+				ensure!(who != auction.seller, Error::<T>::KittyAlreadyOwned);
+				ensure!(
+					<frame_system::Pallet<T>>::block_number() < auction.end,
+					Error::<T>::AuctionEnded
+				);
+				ensure!(amount >= auction.reserve_price, Error::<T>::BidTooLow);
+				if let Some((_, best)) = &auction.best_bid {
+					ensure!(amount > *best, Error::<T>::BidTooLow);
+				}
+
+				T::Currency::reserve(&who, amount.saturating_add(deposit))
+					.map_err(|_| Error::<T>::InsufficientPaymentBalance)?;
+
+				// Refund the outbid bidder
+				if let Some((previous, best)) = auction.best_bid.replace((who.clone(), amount)) {
+					T::Currency::unreserve(&previous, best.saturating_add(deposit));
+				}
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::BidPlaced(who, kitty_id, amount));
+
+			Ok(())
+		}
+
+		/// Cancel an auction that has not received any bid yet.
+		#[pallet::weight(T::WeightInfo::cancel_auction())]
+		pub fn cancel_auction(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let auction = Self::auctions(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+
+			ensure!(who == auction.seller, Error::<T>::NotOwner);
+			ensure!(auction.best_bid.is_none(), Error::<T>::AuctionHasBids);
+
+			Auctions::<T>::remove(kitty_id);
+			AuctionsEndingAt::<T>::mutate(auction.end, |ending| {
+				ending.retain(|id| *id != kitty_id)
+			});
+
+			Self::deposit_event(Event::AuctionCancelled(kitty_id));
+
+			Ok(())
+		}
+
+		/// Lock the kitty in the pallet account and mint `total_shares` shares of it to the
+		/// caller. The kitty can no longer be used until all the shares are redeemed.
+		#[pallet::weight(T::WeightInfo::fractionalize())]
+		pub fn fractionalize(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			total_shares: Shares,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+			ensure!(!total_shares.is_zero(), Error::<T>::InvalidShareAmount);
+
+			// The deposit of the issuer is kept, the vault is not bound by `MaxKittiesOwned`
+			let vault = Self::account_id();
+			Self::kitty_move(&who, &vault, kitty_id);
+
+			Fractions::<T>::insert(kitty_id, Fraction { issuer: who.clone(), total_shares });
+			KittyShares::<T>::insert(kitty_id, &who, total_shares);
+
+			Self::deposit_event(Event::KittyFractionalized(who, kitty_id, total_shares));
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::transfer_shares())]
+		pub fn transfer_shares(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			recipient: <T::Lookup as StaticLookup>::Source,
+			amount: Shares,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let recipient = T::Lookup::lookup(recipient)?;
+
+			ensure!(Fractions::<T>::contains_key(kitty_id), Error::<T>::NotFractionalized);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidShareAmount);
+
+			let balance = Self::shares(kitty_id, &who);
+			ensure!(balance >= amount, Error::<T>::InsufficientShares);
+
+			if who != recipient {
+				if balance == amount {
+					KittyShares::<T>::remove(kitty_id, &who);
+				} else {
+					KittyShares::<T>::insert(kitty_id, &who, balance - amount);
+				}
+				// Cannot overflow, the total supply of a kitty is a `Shares`
+				KittyShares::<T>::mutate(kitty_id, &recipient, |shares| *shares += amount);
+			}
+
+			Self::deposit_event(Event::SharesTransferred(who, recipient, kitty_id, amount));
+
+			Ok(())
+		}
+
+		/// Burn all the shares of a kitty, held by the caller, and take the kitty out of the
+		/// pallet account. The caller reserves the deposit in place of the issuer.
+		#[pallet::weight(T::WeightInfo::redeem())]
+		pub fn redeem(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let fraction = Self::fractions(kitty_id).ok_or(Error::<T>::NotFractionalized)?;
+			ensure!(
+				Self::shares(kitty_id, &who) == fraction.total_shares,
+				Error::<T>::InsufficientShares
+			);
+
+			ensure!(
+				Self::owned_kitties_count(&who) < T::MaxKittiesOwned::get(),
+				Error::<T>::ExceedMaxKittiesOwned
+			);
+			if who != fraction.issuer {
+				T::Currency::reserve(&who, T::ReserveAmount::get())
+					.map_err(|_| Error::<T>::InsufficientReserveBalance)?;
+				T::Currency::unreserve(&fraction.issuer, T::ReserveAmount::get());
+			}
+
+			KittyShares::<T>::remove(kitty_id, &who);
+			Fractions::<T>::remove(kitty_id);
+			Self::kitty_move(&Self::account_id(), &who, kitty_id);
+
+			Self::deposit_event(Event::KittyRedeemed(who, kitty_id));
+
+			Ok(())
+		}
+
+		/// Offer `lessee` the breeding rights of the kitty for `duration` blocks, against
+		/// `price`. The owner keeps the kitty, but cannot transfer, sell, auction or fractionalize
+		/// it until the lease ends.
+		#[pallet::weight(T::WeightInfo::lease())]
+		pub fn lease(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			lessee: <T::Lookup as StaticLookup>::Source,
+			duration: T::BlockNumber,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let lessee = T::Lookup::lookup(lessee)?;
+
+			ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+			ensure!(who != lessee, Error::<T>::KittyAlreadyOwned);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+			ensure!(
+				!duration.is_zero() && duration <= T::MaxLeaseDuration::get(),
+				Error::<T>::InvalidLeaseDuration
+			);
+
+			LeaseOffers::<T>::insert(
+				kitty_id,
+				LeaseOffer { lessee: lessee.clone(), duration, price },
+			);
+
+			Self::deposit_event(Event::LeaseOffered(who, kitty_id, lessee, duration, price));
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::cancel_lease_offer())]
+		pub fn cancel_lease_offer(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+			ensure!(LeaseOffers::<T>::contains_key(kitty_id), Error::<T>::LeaseNotOffered);
+
+			LeaseOffers::<T>::remove(kitty_id);
+
+			Self::deposit_event(Event::LeaseOfferCancelled(who, kitty_id));
+
+			Ok(())
+		}
+
+		/// Accept the lease offered to the caller, paying its price as long as it does not
+		/// exceed `max_price`.
+		#[pallet::weight(T::WeightInfo::accept_lease())]
+		#[transactional]
+		pub fn accept_lease(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let offer = Self::lease_offers(kitty_id)
+				.filter(|offer| offer.lessee == who)
+				.ok_or(Error::<T>::LeaseNotOffered)?;
+			let owner = Owner::<T>::get(kitty_id).ok_or(Error::<T>::KittyWithoutOwner)?;
+
+			// The owner may have changed the terms after the lessee sent the transaction
+			ensure!(offer.price <= max_price, Error::<T>::PriceChanged);
+			// The kitty may have been put up for auction since the offer
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+
+			let end = <frame_system::Pallet<T>>::block_number().saturating_add(offer.duration);
+			LeasesEndingAt::<T>::try_mutate(end, |ending| -> DispatchResult {
+				ensure!(
+					(ending.len() as u32) < T::MaxLeasesPerBlock::get(),
+					Error::<T>::TooManyLeases
+				);
+				ending.push(kitty_id);
+				Ok(())
+			})?;
+
+			T::Currency::transfer(&who, &owner, offer.price, ExistenceRequirement::KeepAlive)?;
+
+			LeaseOffers::<T>::remove(kitty_id);
+			Leases::<T>::insert(kitty_id, Lease { lessee: who.clone(), end });
+			Self::clear_breeding_rights(kitty_id);
+			// A leased kitty cannot be sold
+			Kitties::<T>::mutate(kitty_id, |kitty| {
+				if let Some(k) = kitty {
+					k.list_price = None;
+				}
+			});
+
+			Self::deposit_event(Event::LeaseStarted(owner, kitty_id, who, end));
+
+			Ok(())
+		}
+
+		/// Give the kitty a unique name, reserving `NameDeposit` from the caller. The kitty keeps
+		/// its name when it changes owner.
+		#[pallet::weight(T::WeightInfo::set_name())]
+		pub fn set_name(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			name: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+			ensure!(!name.is_empty(), Error::<T>::InvalidName);
+			let bounded =
+				NameOf::<T>::try_from(name.clone()).map_err(|_| Error::<T>::NameTooLong)?;
+			ensure!(
+				Self::kitty_by_name(&bounded).map_or(true, |id| id == kitty_id),
+				Error::<T>::NameTaken
+			);
+
+			let deposit = T::NameDeposit::get();
+			T::Currency::reserve(&who, deposit)
+				.map_err(|_| Error::<T>::InsufficientReserveBalance)?;
+			if let Some(old) = Self::remove_name(kitty_id) {
+				T::Currency::unreserve(&old.depositor, old.deposit);
+			}
+
+			NamedKitties::<T>::insert(&bounded, kitty_id);
+			KittyNames::<T>::insert(
+				kitty_id,
+				KittyName { name: bounded, depositor: who.clone(), deposit },
+			);
+
+			Self::deposit_event(Event::KittyNamed(who, kitty_id, name));
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::clear_name())]
+		pub fn clear_name(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Some(who) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+
+			let old = Self::remove_name(kitty_id).ok_or(Error::<T>::NameNotSet)?;
+			T::Currency::unreserve(&old.depositor, old.deposit);
+
+			Self::deposit_event(Event::KittyNameCleared(kitty_id));
+
+			Ok(())
+		}
+
+		/// Clear an offensive name, slashing the deposit of the account who set it.
+		#[pallet::weight(T::WeightInfo::force_clear_name())]
+		pub fn force_clear_name(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let old = Self::remove_name(kitty_id).ok_or(Error::<T>::NameNotSet)?;
+			let _ = T::Currency::slash_reserved(&old.depositor, old.deposit);
+
+			Self::deposit_event(Event::KittyNameCleared(kitty_id));
+
+			Ok(())
+		}
+
+		/// Set the metadata URI of the kitty, an empty `metadata` clears it.
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			metadata: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Some(who.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
+
+			if metadata.is_empty() {
+				KittyMetadata::<T>::remove(kitty_id);
+			} else {
+				let bounded =
+					BoundedVec::try_from(metadata).map_err(|_| Error::<T>::MetadataTooLong)?;
+				KittyMetadata::<T>::insert(kitty_id, bounded);
+			}
+
+			Self::deposit_event(Event::KittyMetadataSet(who, kitty_id));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The indexes of all kitties owned by `owner`.
+		pub fn kitties_of(owner: &T::AccountId) -> Vec<T::KittyIndex> {
+			OwnedKitties::<T>::iter_prefix(owner).map(|(kitty_id, _)| kitty_id).collect()
+		}
+
+		/// The family tree of a kitty, going at most `depth` generations back (capped at
+		/// `MAX_FAMILY_TREE_DEPTH`).
+		pub fn family_tree(
+			kitty_id: T::KittyIndex,
+			depth: u32,
+		) -> Option<FamilyTree<T::KittyIndex, T::BlockNumber>> {
+			let kitty = Self::kitties(kitty_id)?;
+			let depth = depth.min(MAX_FAMILY_TREE_DEPTH);
+
+			let parents = match kitty.parents {
+				Some((parent_1, parent_2)) if depth > 0 => [parent_1, parent_2]
+					.iter()
+					.filter_map(|parent| Self::family_tree(*parent, depth - 1))
+					.collect(),
+				_ => Vec::new(),
+			};
+
+			Some(FamilyTree {
+				kitty_id,
+				dna: kitty.dna,
+				generation: kitty.generation,
+				birth_block: kitty.birth_block,
+				parents,
+			})
+		}
+
+		/// The traits of a kitty decoded from its DNA.
+		pub fn kitty_traits(kitty_id: T::KittyIndex) -> Option<KittyTraits> {
+			Self::kitties(kitty_id).map(|kitty| KittyTraits::from_dna(&kitty.dna))
+		}
+
+		// Whether the two kitties are siblings (sharing at least one parent) or parent and child
+		fn are_related(
+			kitty_id_1: T::KittyIndex,
+			kitty_1: &Kitty<T>,
+			kitty_id_2: T::KittyIndex,
+			kitty_2: &Kitty<T>,
+		) -> bool {
+			let is_parent_of = |parent: T::KittyIndex, child: &Kitty<T>| {
+				matches!(child.parents, Some((p1, p2)) if p1 == parent || p2 == parent)
+			};
+
+			let are_siblings = match (kitty_1.parents, kitty_2.parents) {
+				(Some((a1, a2)), Some((b1, b2))) => a1 == b1 || a1 == b2 || a2 == b1 || a2 == b2,
+				_ => false,
+			};
+
+			are_siblings || is_parent_of(kitty_id_1, kitty_2) || is_parent_of(kitty_id_2, kitty_1)
+		}
+
+		// Check the parents can breed, pay the sire owner if needed and create the offspring
+		fn do_breed(
+			who: &T::AccountId,
+			kitty_id_1: T::KittyIndex,
+			kitty_1: &Kitty<T>,
+			kitty_id_2: T::KittyIndex,
+			kitty_2: &Kitty<T>,
+			payment: Option<(&T::AccountId, BalanceOf<T>)>,
+		) -> DispatchResult {
+			ensure!(
+				T::AllowInbreeding::get() ||
+					!Self::are_related(kitty_id_1, kitty_1, kitty_id_2, kitty_2),
+				Error::<T>::InbreedingNotAllowed
+			);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				Self::breeding_ready_at(kitty_id_1) <= now &&
+					Self::breeding_ready_at(kitty_id_2) <= now,
+				Error::<T>::KittyOnCooldown
+			);
+
+			ensure!(
+				KittyTraits::from_dna(&kitty_1.dna)
+					.can_breed_with(&KittyTraits::from_dna(&kitty_2.dna)),
+				Error::<T>::IncompatibleGender
+			);
+
+			let kitty_id = Self::next_kitty_id()?;
+
+			let generation = kitty_1.generation.max(kitty_2.generation).saturating_add(1);
+
+			if let Some((sire_owner, amount)) = payment {
+				T::Currency::transfer(who, sire_owner, amount, ExistenceRequirement::KeepAlive)?;
+			}
+
+			let ready_at = now.saturating_add(T::BreedingCooldown::get());
+			BreedingReadyAt::<T>::insert(kitty_id_1, ready_at);
+			BreedingReadyAt::<T>::insert(kitty_id_2, ready_at);
+
+			Self::new_kitty(
+				who.clone(),
+				kitty_id,
+				Some((kitty_id_1, kitty_id_2)),
+				Some((kitty_1.dna, kitty_2.dna)),
+				generation,
+			)
+		}
+
+		// Create a kitty, or commit to it if its DNA is revealed later on
+		pub(crate) fn new_kitty(
+			who: T::AccountId,
+			kitty_id: T::KittyIndex,
+			parents: Option<(T::KittyIndex, T::KittyIndex)>,
+			genes: Option<([u8; 16], [u8; 16])>,
+			generation: u32,
+		) -> DispatchResult {
+			let delay = T::RevealDelay::get();
+			if delay.is_zero() {
+				let dna = Self::mix_dna(Self::random_value(&who), genes);
+				return Self::kitty_create(who, kitty_id, dna, parents, generation)
+					.map_err(Into::into)
+			}
+
+			let reveal_at = <frame_system::Pallet<T>>::block_number().saturating_add(delay);
+			ensure!(
+				(RevealsAt::<T>::decode_len(reveal_at).unwrap_or(0) as u32) <
+					T::MaxRevealsPerBlock::get(),
+				Error::<T>::TooManyReveals
+			);
+			Self::reserve_kitty(&who)?;
+
+			RevealsAt::<T>::append(reveal_at, kitty_id);
+			KittiesCount::<T>::put(kitty_id);
+			PendingKitties::<T>::insert(
+				kitty_id,
+				PendingKitty { owner: who.clone(), parents, genes, generation, reveal_at },
+			);
+
+			Self::deposit_event(Event::KittyPending(who, kitty_id, reveal_at));
+
+			Ok(())
+		}
+
+		// Draw the DNA of the kitties pending until `n`, returning the number of kitties revealed
+		fn reveal_kitties(n: T::BlockNumber) -> u32 {
+			let mut revealed = 0;
+			for kitty_id in RevealsAt::<T>::take(n) {
+				if let Some(pending) = PendingKitties::<T>::take(kitty_id) {
+					// The randomness depends on the blocks produced since the commitment
+					let (seed, _) = T::Randomness::random(&kitty_id.encode());
+					let selector = (seed, kitty_id).using_encoded(blake2_128);
+					let dna = Self::mix_dna(selector, pending.genes);
+					Self::kitty_insert(
+						pending.owner,
+						kitty_id,
+						dna,
+						pending.parents,
+						pending.generation,
+					);
+					revealed += 1;
+				}
+			}
+			revealed
+		}
+
+		// Use the random `selector` as the DNA of a created kitty, or to pick each bit of a bred
+		// kitty from either parent
+		fn mix_dna(selector: [u8; 16], genes: Option<([u8; 16], [u8; 16])>) -> [u8; 16] {
+			let (dna_1, dna_2) = match genes {
+				Some(genes) => genes,
+				None => return selector,
+			};
+			let mut new_dna = [0u8; 16];
+
+			for i in 0..new_dna.len() {
+				new_dna[i] = (selector[i] & dna_1[i]) | (!selector[i] & dna_2[i]);
+			}
+
+			new_dna
+		}
+
+		// Hand the kitty to the highest bidder, or cancel the auction if that is not possible
+		fn settle_auction(kitty_id: T::KittyIndex, auction: AuctionOf<T>) {
+			if let Some((bidder, amount)) = auction.best_bid {
+				// The deposit is reserved again by the transfer of the kitty
+				T::Currency::unreserve(&bidder, amount.saturating_add(T::ReserveAmount::get()));
+
+				if Self::complete_sale(&auction.seller, &bidder, kitty_id, amount).is_ok() {
+					Self::deposit_event(Event::AuctionSettled(
+						auction.seller,
+						bidder,
+						kitty_id,
+						amount,
+					));
+					return
+				}
+			}
+
+			Self::deposit_event(Event::AuctionCancelled(kitty_id));
+		}
+
+		// Pay the seller and the marketplace fee and transfer the kitty, leaving storage untouched
+		// on failure
+		#[transactional]
+		fn complete_sale(
+			seller: &T::AccountId,
+			buyer: &T::AccountId,
+			kitty_id: T::KittyIndex,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let fee = T::MarketplaceFee::get() * price;
+			if !fee.is_zero() {
+				T::Currency::transfer(
+					buyer,
+					&T::TreasuryAccount::get(),
+					fee,
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+			T::Currency::transfer(
+				buyer,
+				seller,
+				price.saturating_sub(fee),
+				ExistenceRequirement::AllowDeath,
+			)?;
+			Self::kitty_transfer(seller.clone(), buyer.clone(), kitty_id)?;
+			Ok(())
+		}
+
+		pub(crate) fn random_value(sender: &T::AccountId) -> [u8; 16] {
+			let payload = (
+				T::Randomness::random_seed(),
+				&sender,
+				<frame_system::Pallet<T>>::extrinsic_index(),
+			);
+			payload.using_encoded(blake2_128)
+		}
+
+		// Calculate the next kitty index
+		pub(crate) fn next_kitty_id() -> Result<T::KittyIndex, Error<T>> {
+			match Self::kitties_count() {
+				Some(id) => {
+					// Ensure id won't overflow
+					ensure!(id < T::KittyIndex::max_value(), Error::<T>::KittiesCountOverflow);
+					Ok(id.saturating_add(T::KittyIndex::from(1u8)))
+				}
+				// Start count from 1
+				None => Ok(T::KittyIndex::min_value().saturating_add(T::KittyIndex::from(1u8))),
+			}
+		}
+
+		// Setter of kitty count for testing
+		#[cfg(test)]
+		pub fn set_kitty_count(kitty_count: T::KittyIndex) {
+			KittiesCount::<T>::put(kitty_count);
+		}
+
+		// Setter of kitty owner for testing
+		#[cfg(test)]
+		pub fn set_kitty_owner(kitty_index: T::KittyIndex, owner: Option<T::AccountId>) {
+			Owner::<T>::mutate(kitty_index, |v| {
 				*v = owner;
 			});
 		}
 
 		// Create kitty
-		fn kitty_create(
+		pub(crate) fn kitty_create(
 			who: T::AccountId,
 			kitty_id: T::KittyIndex,
 			dna: [u8; 16],
+			parents: Option<(T::KittyIndex, T::KittyIndex)>,
+			generation: u32,
 		) -> Result<(), Error<T>> {
+			Self::reserve_kitty(&who)?;
+
+			KittiesCount::<T>::put(kitty_id);
+			Self::kitty_insert(who, kitty_id, dna, parents, generation);
+
+			Ok(())
+		}
+
+		// Check that `who` can own one more kitty, reserve its deposit and count it, so that
+		// pending kitties take up room as soon as they are committed to
+		fn reserve_kitty(who: &T::AccountId) -> Result<(), Error<T>> {
+			// Ensure the owner has room for one more kitty
+			ensure!(
+				Self::owned_kitties_count(who) < T::MaxKittiesOwned::get(),
+				Error::<T>::ExceedMaxKittiesOwned
+			);
+
 			// Reserve amount of token
-			T::Currency::reserve(&who, T::ReserveAmount::get())
+			T::Currency::reserve(who, T::ReserveAmount::get())
 				.map_err(|_| Error::<T>::InsufficientReserveBalance)?;
 
-			Kitties::<T>::insert(kitty_id, Some(Kitty { dna, list_price: None }));
+			OwnedKittiesCount::<T>::mutate(who, |count| *count += 1);
+
+			Ok(())
+		}
+
+		// Store a kitty whose index is allocated, whose deposit is reserved and which is already
+		// counted for its owner
+		fn kitty_insert(
+			who: T::AccountId,
+			kitty_id: T::KittyIndex,
+			dna: [u8; 16],
+			parents: Option<(T::KittyIndex, T::KittyIndex)>,
+			generation: u32,
+		) {
+			Kitties::<T>::insert(
+				kitty_id,
+				Some(Kitty {
+					dna,
+					list_price: None,
+					sire_price: None,
+					parents,
+					generation,
+					birth_block: <frame_system::Pallet<T>>::block_number(),
+				}),
+			);
 			Owner::<T>::insert(kitty_id, Some(who.clone()));
-			KittiesCount::<T>::put(kitty_id);
+			OwnedKitties::<T>::insert(&who, kitty_id, ());
 
 			Self::deposit_event(Event::KittyCreated(who, kitty_id));
-
-			Ok(())
 		}
 
 		// Transfer kitty ownership and reserved tokens
-		fn kitty_transfer(
+		pub(crate) fn kitty_transfer(
 			sender: T::AccountId,
 			recipient: T::AccountId,
 			kitty_id: T::KittyIndex,
@@ -274,11 +1619,20 @@ pub mod pallet {
 			// Ensure the given kitty belongs to the sender
 			ensure!(Some(sender.clone()) == Owner::<T>::get(kitty_id), Error::<T>::NotOwner);
 
+			// The lease would otherwise bind the new owner
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+
+			// Ensure the recipient has room for one more kitty
+			ensure!(
+				Self::owned_kitties_count(&recipient) < T::MaxKittiesOwned::get(),
+				Error::<T>::ExceedMaxKittiesOwned
+			);
+
 			// Reserve amount of token on recipient
 			T::Currency::reserve(&recipient, T::ReserveAmount::get())
 				.map_err(|_| Error::<T>::InsufficientReserveBalance)?;
 
-			Owner::<T>::insert(kitty_id, Some(recipient.clone()));
+			Self::kitty_move(&sender, &recipient, kitty_id);
 
 			// Unreserve balance of sender
 			T::Currency::unreserve(&sender, T::ReserveAmount::get());
@@ -287,5 +1641,93 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// The account allowed to breed with a kitty: its lessee if it is leased, or its owner.
+		pub fn breeder_of(kitty_id: T::KittyIndex) -> Option<T::AccountId> {
+			Leases::<T>::get(kitty_id)
+				.map(|lease| lease.lessee)
+				.or_else(|| Owner::<T>::get(kitty_id))
+		}
+
+		// Give the breeding rights of the leases ending at `n` back to the owners, returning the
+		// number of leases ended
+		fn expire_leases(n: T::BlockNumber) -> u32 {
+			let mut expired = 0;
+			for kitty_id in LeasesEndingAt::<T>::take(n) {
+				if let Some(lease) = Leases::<T>::take(kitty_id) {
+					Self::clear_breeding_rights(kitty_id);
+					Self::deposit_event(Event::LeaseEnded(lease.lessee, kitty_id));
+					expired += 1;
+				}
+			}
+			expired
+		}
+
+		// Siring approvals and offers were given by the previous breeder
+		fn clear_breeding_rights(kitty_id: T::KittyIndex) {
+			SiringApprovals::<T>::remove(kitty_id);
+			Kitties::<T>::mutate(kitty_id, |kitty| {
+				if let Some(k) = kitty {
+					k.sire_price = None;
+				}
+			});
+		}
+
+		// Free the name of a kitty, returning it along with its deposit, still reserved
+		fn remove_name(kitty_id: T::KittyIndex) -> Option<KittyNameOf<T>> {
+			let old = KittyNames::<T>::take(kitty_id)?;
+			NamedKitties::<T>::remove(&old.name);
+			Some(old)
+		}
+
+		/// The account holding fractionalized kitties.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
+
+		// Change the owner of a kitty without touching deposits
+		fn kitty_move(from: &T::AccountId, to: &T::AccountId, kitty_id: T::KittyIndex) {
+			Owner::<T>::insert(kitty_id, Some(to.clone()));
+			// Listings, approvals and offers were given by the previous owner
+			KittyApprovals::<T>::remove(kitty_id);
+			LeaseOffers::<T>::remove(kitty_id);
+			Kitties::<T>::mutate(kitty_id, |kitty| {
+				if let Some(k) = kitty {
+					k.list_price = None;
+				}
+			});
+			Self::clear_breeding_rights(kitty_id);
+			Self::remove_owned_kitty(from, kitty_id);
+			Self::add_owned_kitty(to, kitty_id);
+		}
+
+		// Remove the kitty and release the reserved tokens of its owner
+		pub(crate) fn kitty_burn(owner: T::AccountId, kitty_id: T::KittyIndex) {
+			Kitties::<T>::remove(kitty_id);
+			Owner::<T>::remove(kitty_id);
+			KittyApprovals::<T>::remove(kitty_id);
+			SiringApprovals::<T>::remove(kitty_id);
+			LeaseOffers::<T>::remove(kitty_id);
+			BreedingReadyAt::<T>::remove(kitty_id);
+			KittyMetadata::<T>::remove(kitty_id);
+			if let Some(old) = Self::remove_name(kitty_id) {
+				T::Currency::unreserve(&old.depositor, old.deposit);
+			}
+			Self::remove_owned_kitty(&owner, kitty_id);
+
+			T::Currency::unreserve(&owner, T::ReserveAmount::get());
+
+			Self::deposit_event(Event::KittyBurned(owner, kitty_id));
+		}
+
+		pub(crate) fn add_owned_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+			OwnedKitties::<T>::insert(owner, kitty_id, ());
+			OwnedKittiesCount::<T>::mutate(owner, |count| *count += 1);
+		}
+
+		fn remove_owned_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+			OwnedKitties::<T>::remove(owner, kitty_id);
+			OwnedKittiesCount::<T>::mutate(owner, |count| *count = count.saturating_sub(1));
+		}
 	}
 }
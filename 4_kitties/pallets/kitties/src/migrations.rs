@@ -0,0 +1,234 @@
+//! Storage migrations for pallet-kitties.
+
+/// Migrations to the layout of storage version 1, from either layout of version 0.
+pub mod v1 {
+	use crate::pallet::{
+		BalanceOf, Config, Kitties, KittiesCount, Kitty, Owner, Pallet, STORAGE_VERSION,
+	};
+	use codec::Decode;
+	use frame_support::{
+		storage::migration::{storage_key_iter, take_storage_value},
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency},
+		weights::Weight,
+		Blake2_128Concat,
+	};
+	use sp_runtime::traits::Saturating;
+	use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, vec::Vec};
+
+	#[derive(Decode)]
+	struct OldKitty([u8; 16]);
+
+	/// `Kitty` of the original `4_kitties` pallet.
+	#[derive(Decode)]
+	struct KittyV0<Balance> {
+		dna: [u8; 16],
+		list_price: Option<Balance>,
+	}
+
+	#[cfg(feature = "try-runtime")]
+	const PRE_MIGRATION_COUNT: &[u8] = b":kitties:v1:pre_migration_count:";
+
+	/// Migrate the kitties of a chain running the original pallet of `4_kitties`, where `Kitty`
+	/// only holds the DNA and list price, to the current layout.
+	///
+	/// Kitties keep their index and deposit, they get no lineage and are indexed by owner.
+	/// Owners above `MaxKittiesOwned` keep their kitties but cannot receive more.
+	pub struct MigrateFromV0<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateFromV0<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut translated: Weight = 0;
+
+			Kitties::<T>::translate::<Option<KittyV0<BalanceOf<T>>>, _>(|_, old| {
+				translated += 1;
+				old.map(|old| {
+					Some(Kitty {
+						dna: old.dna,
+						list_price: old.list_price,
+						sire_price: None,
+						parents: None,
+						generation: 0,
+						birth_block: now,
+					})
+				})
+			});
+
+			let mut indexed: Weight = 0;
+			for (kitty_id, owner) in Owner::<T>::iter() {
+				if let Some(owner) = owner {
+					Pallet::<T>::add_owned_kitty(&owner, kitty_id);
+					indexed += 1;
+				}
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+
+			T::DbWeight::get()
+				.reads_writes(translated + 3 * indexed + 1, translated + 2 * indexed + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::storage::unhashed;
+
+			if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+				return Ok(())
+			}
+
+			let count = Owner::<T>::iter_values().filter(Option::is_some).count() as u32;
+			unhashed::put(PRE_MIGRATION_COUNT, &count);
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			check_migrated::<T>()
+		}
+	}
+
+	/// Migrate the kitties of a chain running the pallet of `5_kitties_app`, where `Kitty` only
+	/// holds the DNA and indexes are `u32` starting at 0, to the current layout.
+	///
+	/// Kitties are re-indexed from 1, indexed by owner, and the `ReserveAmount` deposit is
+	/// reserved from their owners. No kitty is dropped: owners who cannot afford the deposit keep
+	/// their kitties without one, and owners above `MaxKittiesOwned` keep their kitties but cannot
+	/// receive more. `OldPalletName` is the name the pallet had in `construct_runtime!` on the old
+	/// chain, its storage is moved under the current name.
+	pub struct MigrateFromKittiesApp<T, OldPalletName>(PhantomData<(T, OldPalletName)>);
+
+	impl<T: Config, OldPalletName: Get<&'static str>> OnRuntimeUpgrade
+		for MigrateFromKittiesApp<T, OldPalletName>
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let prefix = OldPalletName::get().as_bytes();
+
+			// Drain everything first, the old and new keys may overlap
+			let mut old_kitties: Vec<(u32, Option<OldKitty>)> =
+				storage_key_iter::<u32, Option<OldKitty>, Blake2_128Concat>(prefix, b"Kitties")
+					.drain()
+					.collect();
+			old_kitties.sort_by_key(|(old_id, _)| *old_id);
+			let mut old_owners: BTreeMap<u32, Option<T::AccountId>> =
+				storage_key_iter::<u32, Option<T::AccountId>, Blake2_128Concat>(prefix, b"Owner")
+					.drain()
+					.collect();
+			let old_count = take_storage_value::<u32>(prefix, b"KittiesCount", &[]);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut migrated: Weight = 0;
+			let mut last_id = None;
+
+			for (old_id, kitty) in old_kitties {
+				let (kitty, owner) = match (kitty, old_owners.remove(&old_id).flatten()) {
+					(Some(kitty), Some(owner)) => (kitty, owner),
+					// Kitties without an owner cannot be used anymore
+					_ => continue,
+				};
+
+				let kitty_id = T::KittyIndex::from(old_id).saturating_add(1u32.into());
+
+				Kitties::<T>::insert(
+					kitty_id,
+					Some(Kitty {
+						dna: kitty.0,
+						list_price: None,
+						sire_price: None,
+						parents: None,
+						generation: 0,
+						birth_block: now,
+					}),
+				);
+				Owner::<T>::insert(kitty_id, Some(owner.clone()));
+				Pallet::<T>::add_owned_kitty(&owner, kitty_id);
+
+				// The kitty is kept even if its owner cannot afford the deposit
+				let _ = T::Currency::reserve(&owner, T::ReserveAmount::get());
+
+				last_id = last_id.max(Some(kitty_id));
+				migrated += 1;
+			}
+
+			if old_count.is_some() {
+				match last_id {
+					Some(id) => KittiesCount::<T>::put(id),
+					None => KittiesCount::<T>::kill(),
+				}
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(4 * migrated + 3, 6 * migrated + 3)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::storage::unhashed;
+
+			if Pallet::<T>::on_chain_storage_version() >= STORAGE_VERSION {
+				return Ok(())
+			}
+
+			let prefix = OldPalletName::get().as_bytes();
+			let owners: BTreeMap<u32, Option<T::AccountId>> =
+				storage_key_iter::<u32, Option<T::AccountId>, Blake2_128Concat>(prefix, b"Owner")
+					.collect();
+			// Every kitty with an owner is migrated
+			let count =
+				storage_key_iter::<u32, Option<OldKitty>, Blake2_128Concat>(prefix, b"Kitties")
+					.filter(|(id, kitty)| {
+						kitty.is_some() && matches!(owners.get(id), Some(Some(_)))
+					})
+					.count() as u32;
+
+			unhashed::put(PRE_MIGRATION_COUNT, &count);
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			check_migrated::<T>()
+		}
+	}
+
+	// Check every kitty counted by `pre_upgrade` made it through the migration
+	#[cfg(feature = "try-runtime")]
+	fn check_migrated<T: Config>() -> Result<(), &'static str> {
+		use crate::pallet::OwnedKitties;
+		use frame_support::storage::unhashed;
+
+		if Pallet::<T>::on_chain_storage_version() != STORAGE_VERSION {
+			return Err("pallet-kitties storage version was not updated")
+		}
+
+		if let Some(count) = unhashed::take::<u32>(PRE_MIGRATION_COUNT) {
+			let mut migrated = 0u32;
+			for (kitty_id, kitty) in Kitties::<T>::iter() {
+				if kitty.is_none() {
+					return Err("a migrated kitty is empty")
+				}
+				let owner = Owner::<T>::get(kitty_id).ok_or("a migrated kitty has no owner")?;
+				if !OwnedKitties::<T>::contains_key(&owner, kitty_id) {
+					return Err("a migrated kitty is not indexed by owner")
+				}
+				if KittiesCount::<T>::get().map_or(true, |last| kitty_id > last) {
+					return Err("a migrated kitty is beyond the kitties count")
+				}
+				migrated += 1;
+			}
+			if migrated != count {
+				return Err("some kitties were lost by the migration")
+			}
+		}
+
+		Ok(())
+	}
+}
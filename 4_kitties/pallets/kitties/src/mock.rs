@@ -1,11 +1,12 @@
 use crate as pallet_kitties;
-use frame_support::parameter_types;
+use frame_support::{parameter_types, PalletId};
 use frame_support_test::TestRandomness;
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
 };
 //pub use pallet_balances::Call as BalancesCall;
 
@@ -24,7 +25,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Kitties: pallet_kitties::{Pallet, Call, Storage, Event<T>},
+		Kitties: pallet_kitties::{Pallet, Call, Storage, Event<T>, Config<T>},
 	}
 );
 
@@ -61,6 +62,23 @@ impl system::Config for Test {
 parameter_types! {
 	pub const ExistentialDeposit: u64 = 1;
 	pub const ReserveAmount: u64 = 10;
+	pub const MaxKittiesOwned: u32 = 5;
+	pub const AllowInbreeding: bool = false;
+	pub const BreedingCooldown: u64 = 5;
+	pub const BreedingFee: u64 = 5;
+	pub const MaxAuctionDuration: u64 = 20;
+	pub const MaxAuctionsPerBlock: u32 = 2;
+	pub const MarketplaceFee: Perbill = Perbill::from_percent(10);
+	pub const TreasuryAccount: u64 = 100;
+	pub static MarketplaceEnabled: bool = true;
+	pub const MaxLeaseDuration: u64 = 20;
+	pub const MaxLeasesPerBlock: u32 = 2;
+	pub const KittiesPalletId: PalletId = PalletId(*b"py/kitty");
+	pub const NameDeposit: u64 = 5;
+	pub const MaxNameLength: u32 = 8;
+	pub const MaxMetadataLength: u32 = 32;
+	pub static RevealDelay: u64 = 0;
+	pub const MaxRevealsPerBlock: u32 = 2;
 }
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
@@ -81,6 +99,25 @@ impl pallet_kitties::Config for Test {
 	type Balance = Balance;
 	type Currency = Balances;
 	type ReserveAmount = ReserveAmount;
+	type AllowInbreeding = AllowInbreeding;
+	type BreedingCooldown = BreedingCooldown;
+	type BreedingFee = BreedingFee;
+	type MaxAuctionDuration = MaxAuctionDuration;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MarketplaceEnabled = MarketplaceEnabled;
+	type MarketplaceFee = MarketplaceFee;
+	type TreasuryAccount = TreasuryAccount;
+	type MaxKittiesOwned = MaxKittiesOwned;
+	type MaxLeaseDuration = MaxLeaseDuration;
+	type MaxLeasesPerBlock = MaxLeasesPerBlock;
+	type PalletId = KittiesPalletId;
+	type NameDeposit = NameDeposit;
+	type MaxNameLength = MaxNameLength;
+	type MaxMetadataLength = MaxMetadataLength;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type RevealDelay = RevealDelay;
+	type MaxRevealsPerBlock = MaxRevealsPerBlock;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -0,0 +1,87 @@
+//! Implementations of the FRAME non-fungible token traits, so other pallets can handle kitties
+//! generically. All kitties belong to a single class, `()`.
+
+use crate::pallet::{Auctions, Config, Error, Fractions, Leases, Owner, Pallet};
+use codec::Encode;
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::tokens::nonfungibles::{Inspect, Mutate, Transfer},
+};
+use sp_std::vec::Vec;
+
+/// Attribute holding the SCALE encoded DNA of a kitty.
+pub const DNA_ATTRIBUTE: &[u8] = b"dna";
+/// Attribute holding the SCALE encoded list price of a kitty on sale.
+pub const LIST_PRICE_ATTRIBUTE: &[u8] = b"list_price";
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type InstanceId = T::KittyIndex;
+	type ClassId = ();
+
+	fn owner(_class: &Self::ClassId, instance: &Self::InstanceId) -> Option<T::AccountId> {
+		Owner::<T>::get(instance)
+	}
+
+	fn attribute(
+		_class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		key: &[u8],
+	) -> Option<Vec<u8>> {
+		let kitty = Self::kitties(instance)?;
+
+		match key {
+			DNA_ATTRIBUTE => Some(kitty.dna.encode()),
+			LIST_PRICE_ATTRIBUTE => kitty.list_price.map(|price| price.encode()),
+			_ => None,
+		}
+	}
+
+	fn can_transfer(_class: &Self::ClassId, instance: &Self::InstanceId) -> bool {
+		!Auctions::<T>::contains_key(instance) &&
+			!Fractions::<T>::contains_key(instance) &&
+			!Leases::<T>::contains_key(instance)
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(
+		_class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		destination: &T::AccountId,
+	) -> DispatchResult {
+		let owner = Owner::<T>::get(instance).ok_or(Error::<T>::InvalidKittyIndex)?;
+		ensure!(!Auctions::<T>::contains_key(instance), Error::<T>::KittyInAuction);
+		ensure!(!Fractions::<T>::contains_key(instance), Error::<T>::KittyFractionalized);
+		ensure!(!Leases::<T>::contains_key(instance), Error::<T>::KittyLeased);
+
+		Self::kitty_transfer(owner, destination.clone(), *instance)?;
+
+		Ok(())
+	}
+}
+
+impl<T: Config> Mutate<T::AccountId> for Pallet<T> {
+	/// Mint a new kitty with random DNA, drawn after `RevealDelay` like for `create`. Kitty
+	/// indexes are sequential, so `instance` must be the next free index.
+	fn mint_into(
+		_class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		who: &T::AccountId,
+	) -> DispatchResult {
+		ensure!(*instance == Self::next_kitty_id()?, Error::<T>::InvalidKittyIndex);
+
+		Self::new_kitty(who.clone(), *instance, None, None, 0)
+	}
+
+	fn burn_from(_class: &Self::ClassId, instance: &Self::InstanceId) -> DispatchResult {
+		let owner = Owner::<T>::get(instance).ok_or(Error::<T>::InvalidKittyIndex)?;
+		ensure!(!Auctions::<T>::contains_key(instance), Error::<T>::KittyInAuction);
+		ensure!(!Fractions::<T>::contains_key(instance), Error::<T>::KittyFractionalized);
+		ensure!(!Leases::<T>::contains_key(instance), Error::<T>::KittyLeased);
+
+		Self::kitty_burn(owner, *instance);
+
+		Ok(())
+	}
+}
@@ -1,5 +1,25 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{mock::*, pallet::NameOf, Error, Gender, KittyTraits};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnFinalize, OnInitialize},
+};
+use sp_runtime::DispatchError::BadOrigin;
+use sp_std::convert::TryInto;
+
+fn name(name: &[u8]) -> NameOf<Test> {
+	name.to_vec().try_into().unwrap()
+}
+
+// Overwrite the gender gene of a kitty, which is otherwise fixed by the test randomness
+fn set_gender(kitty_id: KittyIndex, gender: Gender) {
+	crate::Kitties::<Test>::mutate(kitty_id, |kitty| {
+		let kitty = kitty.as_mut().unwrap();
+		kitty.dna[15] = match gender {
+			Gender::Female => 0,
+			Gender::Male => 1,
+		};
+	});
+}
 
 #[test]
 fn create_kitty_succeeds() {
@@ -34,6 +54,30 @@ fn create_kitty_fails_with_count_overflow() {
 	});
 }
 
+#[test]
+fn genesis_kitties_are_minted() {
+	use sp_runtime::BuildStorage;
+
+	let storage = GenesisConfig {
+		balances: pallet_balances::GenesisConfig { balances: vec![(1, 100), (2, 100)] },
+		kitties: crate::GenesisConfig { kitties: vec![(1, [1; 16]), (2, [2; 16]), (1, [3; 16])] },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		assert_eq!(Kitties::kitties_count(), Some(3));
+		assert_eq!(Kitties::kitties(2).unwrap().dna, [2; 16]);
+		assert_eq!(Kitties::owner(3), Some(1));
+		let mut owned = Kitties::kitties_of(&1);
+		owned.sort();
+		assert_eq!(owned, vec![1, 3]);
+		assert_eq!(Balances::reserved_balance(1), 2 * ReserveAmount::get());
+		assert_eq!(Balances::reserved_balance(2), ReserveAmount::get());
+	});
+}
+
 #[test]
 fn transfer_kitty_succeeds() {
 	new_test_ext().execute_with(|| {
@@ -104,6 +148,8 @@ fn breed_kitty_succeeds() {
 		assert_eq!(Kitties::kitties(2).is_some(), true);
 		assert_eq!(Kitties::owner(2), Some(1));
 
+		set_gender(1, Gender::Female);
+		set_gender(2, Gender::Male);
 		assert_ok!(Kitties::breed(Origin::signed(1), 1, 2));
 		assert_eq!(Balances::reserved_balance(1), 3 * ReserveAmount::get());
 
@@ -210,13 +256,17 @@ fn buy_kitty_succeeds() {
 		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
 		assert_eq!(Kitties::kitties(1).unwrap().list_price, Some(50));
 
-		assert_ok!(Kitties::buy(Origin::signed(8), 1));
+		assert_ok!(Kitties::buy(Origin::signed(8), 1, 50));
 		assert_eq!(Balances::reserved_balance(1), 0);
-		assert_eq!(Balances::free_balance(1), 150);
+		// The seller gets the price minus the marketplace fee
+		assert_eq!(Balances::free_balance(1), 145);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), 5);
 		assert_eq!(Balances::reserved_balance(8), ReserveAmount::get());
 		assert_eq!(Balances::free_balance(8), 50 - ReserveAmount::get());
+		assert_eq!(Kitties::kitties(1).unwrap().list_price, None);
 
-		System::assert_last_event(Event::Kitties(crate::Event::KittyTransferred(1, 8, 1)));
+		System::assert_has_event(Event::Kitties(crate::Event::KittyTransferred(1, 8, 1)));
+		System::assert_last_event(Event::Kitties(crate::Event::KittySold(1, 8, 1, 50)));
 	});
 }
 
@@ -237,7 +287,7 @@ fn buy_kitty_fails_with_kitty_no_owner() {
 
 		Kitties::set_kitty_owner(1, None);
 
-		assert_noop!(Kitties::buy(Origin::signed(8), 1), Error::<Test>::KittyWithoutOwner);
+		assert_noop!(Kitties::buy(Origin::signed(8), 1, 50), Error::<Test>::KittyWithoutOwner);
 	});
 }
 
@@ -256,7 +306,7 @@ fn buy_kitty_fails_with_kitty_already_owned() {
 		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
 		assert_eq!(Kitties::kitties(1).unwrap().list_price, Some(50));
 
-		assert_noop!(Kitties::buy(Origin::signed(1), 1), Error::<Test>::KittyAlreadyOwned);
+		assert_noop!(Kitties::buy(Origin::signed(1), 1, 50), Error::<Test>::KittyAlreadyOwned);
 	});
 }
 
@@ -275,7 +325,7 @@ fn buy_kitty_fails_with_kitty_invalid_index() {
 		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
 		assert_eq!(Kitties::kitties(1).unwrap().list_price, Some(50));
 
-		assert_noop!(Kitties::buy(Origin::signed(8), 2), Error::<Test>::InvalidKittyIndex);
+		assert_noop!(Kitties::buy(Origin::signed(8), 2, 50), Error::<Test>::InvalidKittyIndex);
 	});
 }
 
@@ -291,7 +341,7 @@ fn buy_kitty_fails_with_kitty_not_for_sale() {
 		assert_eq!(Kitties::kitties(1).unwrap().list_price, None);
 		assert_eq!(Kitties::owner(1), Some(1));
 
-		assert_noop!(Kitties::buy(Origin::signed(8), 1), Error::<Test>::KittyNotForSale);
+		assert_noop!(Kitties::buy(Origin::signed(8), 1, 50), Error::<Test>::KittyNotForSale);
 	});
 }
 
@@ -310,6 +360,1152 @@ fn buy_kitty_fails_with_payment_failing() {
 		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
 		assert_eq!(Kitties::kitties(1).unwrap().list_price, Some(50));
 
-		assert_noop!(Kitties::buy(Origin::signed(8), 1), Error::<Test>::InsufficientPaymentBalance);
+		assert_noop!(
+			Kitties::buy(Origin::signed(8), 1, 50),
+			Error::<Test>::InsufficientPaymentBalance
+		);
+	});
+}
+
+#[test]
+fn owned_kitties_follow_create_and_transfer() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 8, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_eq!(Kitties::owned_kitties_count(1), 2);
+		assert_eq!(Kitties::owned_kitties(1, 1), Some(()));
+		assert_eq!(Kitties::owned_kitties(1, 2), Some(()));
+
+		assert_ok!(Kitties::transfer(Origin::signed(1), 8, 1));
+		assert_eq!(Kitties::owned_kitties_count(1), 1);
+		assert_eq!(Kitties::owned_kitties_count(8), 1);
+		assert_eq!(Kitties::kitties_of(&1), vec![2]);
+		assert_eq!(Kitties::kitties_of(&8), vec![1]);
+	});
+}
+
+#[test]
+fn create_kitty_fails_with_max_kitties_owned() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		for _ in 0..MaxKittiesOwned::get() {
+			assert_ok!(Kitties::create(Origin::signed(1)));
+		}
+
+		assert_noop!(Kitties::create(Origin::signed(1)), Error::<Test>::ExceedMaxKittiesOwned);
+	});
+}
+
+#[test]
+fn transfer_kitty_fails_with_max_kitties_owned() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 8, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		for _ in 0..MaxKittiesOwned::get() {
+			assert_ok!(Kitties::create(Origin::signed(8)));
+		}
+
+		assert_noop!(
+			Kitties::transfer(Origin::signed(1), 8, 1),
+			Error::<Test>::ExceedMaxKittiesOwned
+		);
+	});
+}
+
+#[test]
+fn breed_kitty_records_lineage() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_eq!(Kitties::kitties(1).unwrap().parents, None);
+		assert_eq!(Kitties::kitties(1).unwrap().generation, 0);
+
+		System::set_block_number(90);
+		set_gender(1, Gender::Female);
+		set_gender(2, Gender::Male);
+		assert_ok!(Kitties::breed(Origin::signed(1), 1, 2));
+
+		let kitty = Kitties::kitties(3).unwrap();
+		assert_eq!(kitty.parents, Some((1, 2)));
+		assert_eq!(kitty.generation, 1);
+		assert_eq!(kitty.birth_block, 90);
+	});
+}
+
+#[test]
+fn breed_kitty_fails_with_inbreeding() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_gender(1, Gender::Female);
+		set_gender(2, Gender::Male);
+		assert_ok!(Kitties::breed(Origin::signed(1), 1, 2));
+		System::set_block_number(86);
+		assert_ok!(Kitties::breed(Origin::signed(1), 1, 2));
+
+		// Parent and child
+		assert_noop!(Kitties::breed(Origin::signed(1), 1, 3), Error::<Test>::InbreedingNotAllowed);
+		assert_noop!(Kitties::breed(Origin::signed(1), 4, 2), Error::<Test>::InbreedingNotAllowed);
+		// Siblings
+		assert_noop!(Kitties::breed(Origin::signed(1), 3, 4), Error::<Test>::InbreedingNotAllowed);
+	});
+}
+
+#[test]
+fn family_tree_works() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_gender(1, Gender::Female);
+		set_gender(2, Gender::Male);
+		assert_ok!(Kitties::breed(Origin::signed(1), 1, 2));
+
+		assert_eq!(Kitties::family_tree(4, 1), None);
+
+		let tree = Kitties::family_tree(3, 1).unwrap();
+		assert_eq!(tree.kitty_id, 3);
+		assert_eq!(tree.generation, 1);
+		assert_eq!(tree.parents.iter().map(|p| p.kitty_id).collect::<Vec<_>>(), vec![1, 2]);
+		assert!(tree.parents.iter().all(|p| p.parents.is_empty()));
+
+		// Depth 0 only returns the kitty itself
+		assert!(Kitties::family_tree(3, 0).unwrap().parents.is_empty());
+	});
+}
+
+#[test]
+fn breed_kitty_fails_with_not_owner() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+
+		// The matron must belong to the breeder
+		assert_noop!(Kitties::breed(Origin::signed(1), 2, 1), Error::<Test>::NotOwner);
+		assert_noop!(Kitties::breed(Origin::signed(3), 2, 3), Error::<Test>::NotOwner);
+		// The sire must belong to the breeder or be approved
+		assert_noop!(Kitties::breed(Origin::signed(1), 1, 2), Error::<Test>::SiringNotApproved);
+	});
+}
+
+#[test]
+fn breed_kitty_with_approved_sire_pays_fee() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+
+		assert_noop!(Kitties::approve_siring(Origin::signed(1), 2, 1), Error::<Test>::NotOwner);
+		assert_ok!(Kitties::approve_siring(Origin::signed(2), 2, 1));
+		System::assert_last_event(Event::Kitties(crate::Event::SiringApproved(2, 2, 1)));
+
+		set_gender(1, Gender::Female);
+		set_gender(2, Gender::Male);
+		assert_ok!(Kitties::breed(Origin::signed(1), 1, 2));
+		assert_eq!(Kitties::owner(3), Some(1));
+		assert_eq!(Balances::free_balance(1), 100 - 2 * ReserveAmount::get() - BreedingFee::get());
+		assert_eq!(Balances::free_balance(2), 100 - ReserveAmount::get() + BreedingFee::get());
+
+		// The approval only covers one breeding
+		assert_eq!(Kitties::siring_approval(2), None);
+		System::set_block_number(90);
+		assert_noop!(Kitties::breed(Origin::signed(1), 1, 2), Error::<Test>::SiringNotApproved);
+	});
+}
+
+#[test]
+fn siring_approval_is_cleared() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 3, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(2)));
+
+		assert_noop!(
+			Kitties::cancel_siring_approval(Origin::signed(2), 1),
+			Error::<Test>::SiringNotApproved
+		);
+		assert_ok!(Kitties::approve_siring(Origin::signed(2), 1, 1));
+		assert_ok!(Kitties::cancel_siring_approval(Origin::signed(2), 1));
+		assert_eq!(Kitties::siring_approval(1), None);
+
+		// Transferring the kitty drops the approvals of the previous owner
+		assert_ok!(Kitties::approve_siring(Origin::signed(2), 1, 1));
+		assert_ok!(Kitties::transfer(Origin::signed(2), 3, 1));
+		assert_eq!(Kitties::siring_approval(1), None);
+	});
+}
+
+#[test]
+fn breed_kitty_fails_with_cooldown() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_gender(1, Gender::Female);
+		set_gender(2, Gender::Male);
+		set_gender(3, Gender::Male);
+		assert_ok!(Kitties::breed(Origin::signed(1), 1, 2));
+		assert_eq!(Kitties::breeding_ready_at(1), 81 + BreedingCooldown::get());
+
+		// Either parent resting blocks the breeding
+		assert_noop!(Kitties::breed(Origin::signed(1), 3, 1), Error::<Test>::KittyOnCooldown);
+		assert_noop!(Kitties::breed(Origin::signed(1), 2, 3), Error::<Test>::KittyOnCooldown);
+
+		System::set_block_number(81 + BreedingCooldown::get());
+		assert_ok!(Kitties::breed(Origin::signed(1), 3, 1));
+	});
+}
+
+#[test]
+fn breed_with_sire_succeeds() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+
+		assert_ok!(Kitties::offer_sire(Origin::signed(2), 2, 20));
+		System::assert_last_event(Event::Kitties(crate::Event::SireOffered(2, 2, 20)));
+
+		set_gender(1, Gender::Female);
+		set_gender(2, Gender::Male);
+		assert_ok!(Kitties::breed_with_sire(Origin::signed(1), 1, 2));
+
+		// The offspring goes to the breeder and the sire stays with its owner
+		assert_eq!(Kitties::owner(3), Some(1));
+		assert_eq!(Kitties::owner(2), Some(2));
+		assert_eq!(Kitties::kitties(3).unwrap().parents, Some((1, 2)));
+		assert_eq!(Balances::free_balance(1), 100 - 2 * ReserveAmount::get() - 20);
+		assert_eq!(Balances::free_balance(2), 100 - ReserveAmount::get() + 20);
+
+		// The offer stays open for later breedings
+		assert_eq!(Kitties::kitties(2).unwrap().sire_price, Some(20));
+	});
+}
+
+#[test]
+fn breed_with_sire_fails() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+
+		assert_noop!(Kitties::offer_sire(Origin::signed(1), 2, 20), Error::<Test>::NotOwner);
+		assert_noop!(
+			Kitties::offer_sire(Origin::signed(1), 9, 20),
+			Error::<Test>::InvalidKittyIndex
+		);
+
+		assert_noop!(
+			Kitties::breed_with_sire(Origin::signed(1), 1, 2),
+			Error::<Test>::SireNotOffered
+		);
+
+		assert_ok!(Kitties::offer_sire(Origin::signed(2), 2, 200));
+		assert_noop!(Kitties::breed_with_sire(Origin::signed(2), 1, 2), Error::<Test>::NotOwner);
+		assert_noop!(
+			Kitties::breed_with_sire(Origin::signed(1), 1, 3),
+			Error::<Test>::KittyAlreadyOwned
+		);
+		set_gender(1, Gender::Female);
+		set_gender(2, Gender::Male);
+		assert_noop!(
+			Kitties::breed_with_sire(Origin::signed(1), 1, 2),
+			pallet_balances::Error::<Test, _>::InsufficientBalance
+		);
+
+		// Transferring the sire withdraws the offer of the previous owner
+		assert_ok!(Kitties::transfer(Origin::signed(2), 1, 2));
+		assert_eq!(Kitties::kitties(2).unwrap().sire_price, None);
+	});
+}
+
+#[test]
+fn auction_is_settled_to_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 3, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create_auction(Origin::signed(1), 1, 20, 5));
+		System::assert_last_event(Event::Kitties(crate::Event::AuctionCreated(1, 1, 20, 86)));
+		assert_eq!(Kitties::auctions_ending_at(86), vec![1]);
+
+		assert_noop!(Kitties::bid(Origin::signed(1), 1, 30), Error::<Test>::KittyAlreadyOwned);
+		assert_noop!(Kitties::bid(Origin::signed(2), 1, 10), Error::<Test>::BidTooLow);
+		assert_noop!(
+			Kitties::bid(Origin::signed(2), 1, 200),
+			Error::<Test>::InsufficientPaymentBalance
+		);
+		// The deposit of the kitty is reserved with the bid
+		assert_noop!(
+			Kitties::bid(Origin::signed(2), 1, 95),
+			Error::<Test>::InsufficientPaymentBalance
+		);
+
+		// Bidders must be able to take the kitty
+		let _ = Balances::set_balance(Origin::root(), 4, 100, 0);
+		for _ in 0..MaxKittiesOwned::get() {
+			assert_ok!(Kitties::create(Origin::signed(4)));
+		}
+		assert_noop!(Kitties::bid(Origin::signed(4), 1, 20), Error::<Test>::ExceedMaxKittiesOwned);
+
+		assert_ok!(Kitties::bid(Origin::signed(2), 1, 20));
+		assert_eq!(Balances::reserved_balance(2), 20 + ReserveAmount::get());
+		assert_noop!(Kitties::bid(Origin::signed(3), 1, 20), Error::<Test>::BidTooLow);
+
+		// The outbid bidder is refunded
+		assert_ok!(Kitties::bid(Origin::signed(3), 1, 30));
+		System::assert_last_event(Event::Kitties(crate::Event::BidPlaced(3, 1, 30)));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::reserved_balance(3), 30 + ReserveAmount::get());
+
+		// The kitty is locked in the auction
+		assert_noop!(Kitties::cancel_auction(Origin::signed(1), 1), Error::<Test>::AuctionHasBids);
+		assert_noop!(Kitties::transfer(Origin::signed(1), 2, 1), Error::<Test>::KittyInAuction);
+		assert_noop!(Kitties::sell(Origin::signed(1), 1, 5), Error::<Test>::KittyInAuction);
+
+		System::set_block_number(86);
+		assert_noop!(Kitties::bid(Origin::signed(2), 1, 40), Error::<Test>::AuctionEnded);
+
+		Kitties::on_finalize(86);
+		System::assert_last_event(Event::Kitties(crate::Event::AuctionSettled(1, 3, 1, 30)));
+		assert_eq!(Kitties::owner(1), Some(3));
+		assert_eq!(Kitties::auctions(1), None);
+		assert_eq!(Balances::free_balance(1), 100 + 30 - 3);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), 3);
+		assert_eq!(Balances::free_balance(3), 100 - 30 - ReserveAmount::get());
+		assert_eq!(Balances::reserved_balance(3), ReserveAmount::get());
+	});
+}
+
+#[test]
+fn auction_is_cancelled_without_bids() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+
+		assert_ok!(Kitties::create_auction(Origin::signed(1), 1, 20, 5));
+		assert_noop!(Kitties::cancel_auction(Origin::signed(2), 1), Error::<Test>::NotOwner);
+		assert_ok!(Kitties::cancel_auction(Origin::signed(1), 1));
+		System::assert_last_event(Event::Kitties(crate::Event::AuctionCancelled(1)));
+		assert_eq!(Kitties::auctions(1), None);
+		assert!(Kitties::auctions_ending_at(86).is_empty());
+		assert_noop!(Kitties::cancel_auction(Origin::signed(1), 1), Error::<Test>::AuctionNotFound);
+
+		// An auction ending without bids leaves the kitty with the seller
+		assert_ok!(Kitties::create_auction(Origin::signed(1), 2, 20, 5));
+		Kitties::on_finalize(86);
+		System::assert_last_event(Event::Kitties(crate::Event::AuctionCancelled(2)));
+		assert_eq!(Kitties::owner(2), Some(1));
+		assert_eq!(Kitties::auctions(2), None);
+	});
+}
+
+#[test]
+fn create_auction_fails() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+
+		assert_noop!(Kitties::create_auction(Origin::signed(2), 1, 20, 5), Error::<Test>::NotOwner);
+		assert_noop!(
+			Kitties::create_auction(Origin::signed(1), 9, 20, 5),
+			Error::<Test>::InvalidKittyIndex
+		);
+		assert_noop!(
+			Kitties::create_auction(Origin::signed(1), 1, 20, 0),
+			Error::<Test>::InvalidAuctionDuration
+		);
+		assert_noop!(
+			Kitties::create_auction(Origin::signed(1), 1, 20, MaxAuctionDuration::get() + 1),
+			Error::<Test>::InvalidAuctionDuration
+		);
+
+		assert_ok!(Kitties::create_auction(Origin::signed(1), 1, 20, 5));
+		assert_noop!(
+			Kitties::create_auction(Origin::signed(1), 1, 20, 5),
+			Error::<Test>::KittyInAuction
+		);
+		assert_ok!(Kitties::create_auction(Origin::signed(1), 2, 20, 5));
+		assert_noop!(
+			Kitties::create_auction(Origin::signed(1), 3, 20, 5),
+			Error::<Test>::TooManyAuctions
+		);
+	});
+}
+
+#[test]
+fn sell_kitty_updates_price() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 60));
+
+		assert_eq!(Kitties::kitties(1).unwrap().list_price, Some(60));
+		System::assert_last_event(Event::Kitties(crate::Event::KittyPriceUpdated(1, 1, 50, 60)));
+	});
+}
+
+#[test]
+fn cancel_sale_succeeds() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 8, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_noop!(Kitties::cancel_sale(Origin::signed(1), 1), Error::<Test>::KittyNotForSale);
+		assert_noop!(Kitties::cancel_sale(Origin::signed(1), 2), Error::<Test>::InvalidKittyIndex);
+
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
+		assert_noop!(Kitties::cancel_sale(Origin::signed(8), 1), Error::<Test>::NotOwner);
+		assert_ok!(Kitties::cancel_sale(Origin::signed(1), 1));
+		System::assert_last_event(Event::Kitties(crate::Event::KittyDelisted(1, 1)));
+
+		assert_eq!(Kitties::kitties(1).unwrap().list_price, None);
+		assert_noop!(Kitties::buy(Origin::signed(8), 1, 50), Error::<Test>::KittyNotForSale);
+	});
+}
+
+#[test]
+fn transfer_kitty_clears_listing() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 8, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
+		assert_ok!(Kitties::transfer(Origin::signed(1), 8, 1));
+
+		assert_eq!(Kitties::kitties(1).unwrap().list_price, None);
+		assert_noop!(Kitties::buy(Origin::signed(1), 1, 50), Error::<Test>::KittyNotForSale);
+	});
+}
+
+#[test]
+fn marketplace_can_be_disabled() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 8, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
+
+		MarketplaceEnabled::set(false);
+		assert_noop!(Kitties::sell(Origin::signed(1), 1, 60), Error::<Test>::MarketplaceDisabled);
+		assert_noop!(Kitties::buy(Origin::signed(8), 1, 50), Error::<Test>::MarketplaceDisabled);
+		assert_noop!(
+			Kitties::offer_sire(Origin::signed(1), 1, 10),
+			Error::<Test>::MarketplaceDisabled
+		);
+		assert_noop!(
+			Kitties::create_auction(Origin::signed(1), 1, 10, 5),
+			Error::<Test>::MarketplaceDisabled
+		);
+
+		// Listings made before can still be withdrawn, and kitties given away
+		assert_ok!(Kitties::cancel_sale(Origin::signed(1), 1));
+		assert_ok!(Kitties::transfer(Origin::signed(1), 8, 1));
+		MarketplaceEnabled::set(true);
+	});
+}
+
+#[test]
+fn buy_kitty_fails_with_price_changed() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 8, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
+
+		// The seller raises the price before the purchase is executed
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 60));
+		assert_noop!(Kitties::buy(Origin::signed(8), 1, 50), Error::<Test>::PriceChanged);
+
+		// Paying less than the agreed maximum is fine
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 40));
+		assert_ok!(Kitties::buy(Origin::signed(8), 1, 50));
+		System::assert_last_event(Event::Kitties(crate::Event::KittySold(1, 8, 1, 40)));
+	});
+}
+
+#[test]
+fn kitty_traits_are_decoded_from_dna() {
+	let mut dna = [0u8; 16];
+	dna[0] = 17;
+	dna[1] = 3;
+	dna[2] = 23;
+	dna[3] = 25;
+	dna[4] = 14;
+	dna[6] = 0b0000_0100;
+	dna[15] = 7;
+
+	let traits = KittyTraits::from_dna(&dna);
+	assert_eq!(traits.body_color, 2);
+	assert_eq!(traits.eye_shape, 3);
+	assert_eq!(traits.accessory, 3);
+	assert_eq!(traits.pattern, 5);
+	assert_eq!(traits.mouth, 4);
+	assert_eq!(traits.rarity_score, 13);
+	assert_eq!(traits.gender, Gender::Male);
+
+	dna[15] = 8;
+	assert_eq!(KittyTraits::from_dna(&dna).gender, Gender::Female);
+}
+
+#[test]
+fn breed_kitty_fails_with_incompatible_gender() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		set_gender(1, Gender::Male);
+		set_gender(2, Gender::Male);
+
+		assert_eq!(Kitties::kitty_traits(1).unwrap().gender, Gender::Male);
+		assert_eq!(Kitties::kitty_traits(3), None);
+		assert_noop!(Kitties::breed(Origin::signed(1), 1, 2), Error::<Test>::IncompatibleGender);
+
+		set_gender(2, Gender::Female);
+		assert_ok!(Kitties::breed(Origin::signed(1), 1, 2));
+	});
+}
+
+#[test]
+fn burn_kitty_succeeds() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 2 * ReserveAmount::get());
+
+		assert_ok!(Kitties::burn(Origin::signed(1), 1));
+		System::assert_last_event(Event::Kitties(crate::Event::KittyBurned(1, 1)));
+
+		assert_eq!(Kitties::kitties(1).is_none(), true);
+		assert_eq!(Kitties::owner(1), None);
+		assert_eq!(Kitties::kitties_of(&1), vec![2]);
+		assert_eq!(Kitties::owned_kitties_count(1), 1);
+		assert_eq!(Balances::reserved_balance(1), ReserveAmount::get());
+
+		// Indexes of burned kitties are not reused
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_eq!(Kitties::owner(3), Some(1));
+	});
+}
+
+#[test]
+fn burn_kitty_fails() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+
+		assert_noop!(Kitties::burn(Origin::signed(1), 2), Error::<Test>::InvalidKittyIndex);
+		assert_noop!(Kitties::burn(Origin::signed(2), 1), Error::<Test>::NotOwner);
+
+		assert_ok!(Kitties::create_auction(Origin::signed(1), 1, 20, 5));
+		assert_noop!(Kitties::burn(Origin::signed(1), 1), Error::<Test>::KittyInAuction);
+	});
+}
+
+#[test]
+fn transfer_from_with_kitty_approval() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 3, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+
+		assert_noop!(
+			Kitties::transfer_from(Origin::signed(2), 1, 3, 1),
+			Error::<Test>::NotApproved
+		);
+		assert_noop!(Kitties::approve(Origin::signed(2), 1, Some(2)), Error::<Test>::NotApproved);
+		assert_noop!(
+			Kitties::approve(Origin::signed(1), 2, Some(2)),
+			Error::<Test>::InvalidKittyIndex
+		);
+
+		assert_ok!(Kitties::approve(Origin::signed(1), 1, Some(2)));
+		System::assert_last_event(Event::Kitties(crate::Event::Approval(1, Some(2), 1)));
+
+		assert_ok!(Kitties::transfer_from(Origin::signed(2), 1, 3, 1));
+		assert_eq!(Kitties::owner(1), Some(3));
+
+		// The approval does not survive the transfer
+		assert_eq!(Kitties::kitty_approval(1), None);
+		assert_noop!(
+			Kitties::transfer_from(Origin::signed(2), 3, 1, 1),
+			Error::<Test>::NotApproved
+		);
+	});
+}
+
+#[test]
+fn approval_can_be_cleared() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::approve(Origin::signed(1), 1, Some(2)));
+		assert_ok!(Kitties::approve(Origin::signed(1), 1, None));
+		System::assert_last_event(Event::Kitties(crate::Event::Approval(1, None, 1)));
+
+		assert_noop!(
+			Kitties::transfer_from(Origin::signed(2), 1, 2, 1),
+			Error::<Test>::NotApproved
+		);
+	});
+}
+
+#[test]
+fn transfer_from_with_operator() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 3, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(1)));
+
+		assert_ok!(Kitties::set_approval_for_all(Origin::signed(1), 2, true));
+		System::assert_last_event(Event::Kitties(crate::Event::ApprovalForAll(1, 2, true)));
+
+		// Operators can move any kitty of the owner and approve others
+		assert_ok!(Kitties::transfer_from(Origin::signed(2), 1, 3, 1));
+		assert_eq!(Kitties::owner(1), Some(3));
+		assert_ok!(Kitties::approve(Origin::signed(2), 2, Some(4)));
+		assert_eq!(Kitties::kitty_approval(2), Some(4));
+
+		// `from` must own the kitty
+		assert_noop!(Kitties::transfer_from(Origin::signed(2), 1, 3, 1), Error::<Test>::NotOwner);
+
+		assert_ok!(Kitties::set_approval_for_all(Origin::signed(1), 2, false));
+		assert_noop!(
+			Kitties::transfer_from(Origin::signed(2), 1, 3, 2),
+			Error::<Test>::NotApproved
+		);
+	});
+}
+
+#[test]
+fn nonfungibles_inspect_works() {
+	use codec::Encode;
+	use frame_support::traits::tokens::nonfungibles::Inspect;
+
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
+
+		assert_eq!(<Kitties as Inspect<u64>>::owner(&(), &1), Some(1));
+		assert_eq!(<Kitties as Inspect<u64>>::owner(&(), &2), None);
+
+		let dna = Kitties::kitties(1).unwrap().dna;
+		assert_eq!(
+			<Kitties as Inspect<u64>>::attribute(&(), &1, crate::nonfungibles::DNA_ATTRIBUTE),
+			Some(dna.encode())
+		);
+		assert_eq!(
+			<Kitties as Inspect<u64>>::attribute(
+				&(),
+				&1,
+				crate::nonfungibles::LIST_PRICE_ATTRIBUTE
+			),
+			Some(50u64.encode())
+		);
+		assert_eq!(<Kitties as Inspect<u64>>::attribute(&(), &1, b"unknown"), None);
+
+		assert!(<Kitties as Inspect<u64>>::can_transfer(&(), &1));
+		assert_ok!(Kitties::cancel_sale(Origin::signed(1), 1));
+		assert_ok!(Kitties::create_auction(Origin::signed(1), 1, 20, 5));
+		assert!(!<Kitties as Inspect<u64>>::can_transfer(&(), &1));
+	});
+}
+
+#[test]
+fn nonfungibles_transfer_and_mutate_work() {
+	use frame_support::traits::tokens::nonfungibles::{Mutate, Transfer};
+
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+
+		// Indexes are handed out in order
+		assert_noop!(
+			<Kitties as Mutate<u64>>::mint_into(&(), &2, &1),
+			Error::<Test>::InvalidKittyIndex
+		);
+		assert_ok!(<Kitties as Mutate<u64>>::mint_into(&(), &1, &1));
+		assert_eq!(Kitties::owner(1), Some(1));
+		assert_eq!(Balances::reserved_balance(1), ReserveAmount::get());
+
+		assert_ok!(<Kitties as Transfer<u64>>::transfer(&(), &1, &2));
+		assert_eq!(Kitties::owner(1), Some(2));
+		assert_noop!(
+			<Kitties as Transfer<u64>>::transfer(&(), &3, &2),
+			Error::<Test>::InvalidKittyIndex
+		);
+
+		assert_ok!(<Kitties as Mutate<u64>>::burn_from(&(), &1));
+		assert_eq!(Kitties::kitties(1).is_none(), true);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn migration_from_v0_works() {
+	use crate::migrations::v1::MigrateFromV0;
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::put_storage_value,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	new_test_ext().execute_with(|| {
+		// Layout of the original `4_kitties` pallet: `Kitty` holds the DNA and list price
+		let old_kitty = |id: u32, owner: u64, list_price: Option<Balance>| {
+			let key = Blake2_128Concat::hash(&id.encode());
+			put_storage_value(b"Kitties", b"Kitties", &key, Some(([id as u8; 16], list_price)));
+			put_storage_value(b"Kitties", b"Owner", &key, Some(owner));
+		};
+		old_kitty(1, 1, None);
+		old_kitty(2, 2, Some(50));
+		old_kitty(3, 1, None);
+		Kitties::set_kitty_count(3);
+
+		MigrateFromV0::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Kitties::on_chain_storage_version(), StorageVersion::new(1));
+		let kitty = Kitties::kitties(2).unwrap();
+		assert_eq!(kitty.dna, [2; 16]);
+		assert_eq!(kitty.list_price, Some(50));
+		assert_eq!(kitty.parents, None);
+		assert_eq!(kitty.generation, 0);
+		assert_eq!(kitty.birth_block, 81);
+		assert_eq!(Kitties::kitties_of(&1).len(), 2);
+		assert_eq!(Kitties::owned_kitties_count(1), 2);
+		assert_eq!(Kitties::kitties_of(&2), vec![2]);
+
+		// The kitties can be used again, and running it again is a no-op
+		let _ = Balances::set_balance(Origin::root(), 3, 100, 0);
+		assert_ok!(Kitties::buy(Origin::signed(3), 2, 50));
+		MigrateFromV0::<Test>::on_runtime_upgrade();
+		assert_eq!(Kitties::owned_kitties_count(1), 2);
+		assert_eq!(Kitties::kitties_of(&3), vec![2]);
+	});
+}
+
+#[test]
+fn migration_from_kitties_app_works() {
+	use crate::migrations::v1::MigrateFromKittiesApp;
+	use codec::Encode;
+	use frame_support::{
+		parameter_types,
+		storage::migration::put_storage_value,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	parameter_types! {
+		pub const OldPalletName: &'static str = "KittiesModule";
+	}
+
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+
+		// Layout of the `5_kitties_app` pallet: indexes from 0 and a DNA only `Kitty`
+		let old_kitty = |id: u32, owner: Option<u64>| {
+			let key = Blake2_128Concat::hash(&id.encode());
+			put_storage_value(b"KittiesModule", b"Kitties", &key, Some([id as u8; 16]));
+			put_storage_value(b"KittiesModule", b"Owner", &key, owner);
+		};
+		old_kitty(0, Some(1));
+		old_kitty(1, Some(2));
+		old_kitty(2, None);
+		// 3 cannot afford the deposit
+		old_kitty(3, Some(3));
+		// 2 ends up above the cap
+		for id in 4..9 {
+			old_kitty(id, Some(2));
+		}
+		put_storage_value(b"KittiesModule", b"KittiesCount", &[], 9u32);
+
+		MigrateFromKittiesApp::<Test, OldPalletName>::on_runtime_upgrade();
+
+		assert_eq!(Kitties::on_chain_storage_version(), StorageVersion::new(1));
+		assert_eq!(Kitties::kitties_count(), Some(9));
+		assert_eq!(Kitties::kitties(1).unwrap().dna, [0; 16]);
+		assert_eq!(Kitties::kitties(2).unwrap().dna, [1; 16]);
+		assert_eq!(Kitties::kitties(3).is_none(), true);
+		assert_eq!(Kitties::owner(1), Some(1));
+		assert_eq!(Kitties::owner(2), Some(2));
+		assert_eq!(crate::OwnedKitties::<Test>::contains_key(1, 1), true);
+		assert_eq!(crate::OwnedKitties::<Test>::contains_key(2, 2), true);
+		assert_eq!(Balances::reserved_balance(1), ReserveAmount::get());
+
+		// No kitty is dropped, even without a deposit or above the cap
+		assert_eq!(Kitties::owner(4), Some(3));
+		assert_eq!(Kitties::owned_kitties_count(3), 1);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Kitties::owner(9), Some(2));
+		assert_eq!(Kitties::owned_kitties_count(2), MaxKittiesOwned::get() + 1);
+		assert_eq!(
+			Balances::reserved_balance(2),
+			ReserveAmount::get() * (MaxKittiesOwned::get() + 1) as u64
+		);
+		assert_noop!(Kitties::create(Origin::signed(2)), Error::<Test>::ExceedMaxKittiesOwned);
+
+		// The old storage is gone and new kitties continue the indexes
+		assert_eq!(
+			frame_support::storage::migration::get_storage_value::<u32>(
+				b"KittiesModule",
+				b"KittiesCount",
+				&[]
+			),
+			None
+		);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_eq!(Kitties::owner(10), Some(1));
+
+		// Running it again is a no-op
+		let _ = Balances::set_balance(Origin::root(), 3, 100, 0);
+		old_kitty(0, Some(3));
+		MigrateFromKittiesApp::<Test, OldPalletName>::on_runtime_upgrade();
+		assert_eq!(Balances::reserved_balance(3), 0);
+	});
+}
+
+#[test]
+fn fractionalize_and_redeem_kitty() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 50));
+		assert_noop!(Kitties::fractionalize(Origin::signed(2), 1, 100), Error::<Test>::NotOwner);
+		assert_noop!(
+			Kitties::fractionalize(Origin::signed(1), 1, 0),
+			Error::<Test>::InvalidShareAmount
+		);
+		assert_ok!(Kitties::fractionalize(Origin::signed(1), 1, 100));
+		System::assert_last_event(Event::Kitties(crate::Event::KittyFractionalized(1, 1, 100)));
+
+		// The kitty is locked in the pallet account, the issuer keeps the deposit reserved
+		assert_eq!(Kitties::owner(1), Some(Kitties::account_id()));
+		assert_eq!(Kitties::kitties(1).unwrap().list_price, None);
+		assert_eq!(Kitties::shares(1, 1), 100);
+		assert_eq!(Balances::reserved_balance(1), ReserveAmount::get());
+		assert_noop!(Kitties::transfer(Origin::signed(1), 2, 1), Error::<Test>::NotOwner);
+
+		assert_noop!(
+			Kitties::transfer_shares(Origin::signed(1), 1, 2, 101),
+			Error::<Test>::InsufficientShares
+		);
+		assert_noop!(
+			Kitties::transfer_shares(Origin::signed(1), 2, 2, 1),
+			Error::<Test>::NotFractionalized
+		);
+		assert_ok!(Kitties::transfer_shares(Origin::signed(1), 1, 2, 60));
+		System::assert_last_event(Event::Kitties(crate::Event::SharesTransferred(1, 2, 1, 60)));
+		assert_eq!(Kitties::shares(1, 1), 40);
+		assert_eq!(Kitties::shares(1, 2), 60);
+
+		// Only the holder of every share can redeem the kitty
+		assert_noop!(Kitties::redeem(Origin::signed(2), 1), Error::<Test>::InsufficientShares);
+		assert_ok!(Kitties::transfer_shares(Origin::signed(1), 1, 2, 40));
+		assert_ok!(Kitties::redeem(Origin::signed(2), 1));
+		System::assert_last_event(Event::Kitties(crate::Event::KittyRedeemed(2, 1)));
+
+		assert_eq!(Kitties::owner(1), Some(2));
+		assert_eq!(Kitties::fractions(1), None);
+		assert_eq!(Kitties::shares(1, 2), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), ReserveAmount::get());
+		assert_noop!(Kitties::redeem(Origin::signed(2), 1), Error::<Test>::NotFractionalized);
+	});
+}
+
+#[test]
+fn lease_kitty_grants_breeding_rights() {
+	use frame_support::traits::tokens::nonfungibles::Inspect;
+
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+		set_gender(1, Gender::Male);
+		set_gender(2, Gender::Female);
+
+		assert_noop!(Kitties::lease(Origin::signed(2), 1, 2, 10, 20), Error::<Test>::NotOwner);
+		assert_noop!(
+			Kitties::lease(Origin::signed(1), 1, 2, 0, 20),
+			Error::<Test>::InvalidLeaseDuration
+		);
+		assert_noop!(
+			Kitties::lease(Origin::signed(1), 1, 2, MaxLeaseDuration::get() + 1, 20),
+			Error::<Test>::InvalidLeaseDuration
+		);
+		assert_ok!(Kitties::lease(Origin::signed(1), 1, 2, 10, 20));
+		System::assert_last_event(Event::Kitties(crate::Event::LeaseOffered(1, 1, 2, 10, 20)));
+
+		assert_noop!(
+			Kitties::accept_lease(Origin::signed(3), 1, 20),
+			Error::<Test>::LeaseNotOffered
+		);
+		assert_noop!(Kitties::accept_lease(Origin::signed(2), 1, 10), Error::<Test>::PriceChanged);
+		assert_ok!(Kitties::accept_lease(Origin::signed(2), 1, 20));
+		System::assert_last_event(Event::Kitties(crate::Event::LeaseStarted(1, 1, 2, 91)));
+		assert_eq!(Balances::free_balance(1), 120 - ReserveAmount::get());
+		assert_eq!(Balances::free_balance(2), 80 - ReserveAmount::get());
+
+		// The lessee breeds with the kitty, the owner keeps the title but cannot use it
+		assert_eq!(Kitties::breeder_of(1), Some(2));
+		assert_ok!(Kitties::breed(Origin::signed(2), 2, 1));
+		assert_eq!(Kitties::owner(3), Some(2));
+		assert_noop!(Kitties::offer_sire(Origin::signed(1), 1, 10), Error::<Test>::NotOwner);
+		assert_noop!(Kitties::burn(Origin::signed(1), 1), Error::<Test>::KittyLeased);
+		assert_noop!(Kitties::lease(Origin::signed(1), 1, 3, 10, 20), Error::<Test>::KittyLeased);
+		assert_eq!(Kitties::owner(1), Some(1));
+
+		// Nor can it change hands until the lease ends
+		assert_noop!(Kitties::transfer(Origin::signed(1), 3, 1), Error::<Test>::KittyLeased);
+		assert_noop!(
+			Kitties::transfer_from(Origin::signed(1), 1, 3, 1),
+			Error::<Test>::KittyLeased
+		);
+		assert_noop!(Kitties::sell(Origin::signed(1), 1, 10), Error::<Test>::KittyLeased);
+		assert_noop!(
+			Kitties::create_auction(Origin::signed(1), 1, 10, 10),
+			Error::<Test>::KittyLeased
+		);
+		assert_noop!(Kitties::fractionalize(Origin::signed(1), 1, 10), Error::<Test>::KittyLeased);
+		assert_eq!(<Kitties as Inspect<u64>>::can_transfer(&(), &1), false);
+
+		// The breeding rights go back to the owner when the lease ends
+		System::set_block_number(91);
+		Kitties::on_initialize(91);
+		System::assert_last_event(Event::Kitties(crate::Event::LeaseEnded(2, 1)));
+		assert_eq!(Kitties::leases(1), None);
+		assert_eq!(Kitties::breeder_of(1), Some(1));
+		assert_ok!(Kitties::offer_sire(Origin::signed(1), 1, 10));
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 1));
+	});
+}
+
+#[test]
+fn lease_withdraws_listing() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 3, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::sell(Origin::signed(1), 1, 10));
+		assert_ok!(Kitties::lease(Origin::signed(1), 1, 2, 10, 20));
+		assert_ok!(Kitties::accept_lease(Origin::signed(2), 1, 20));
+
+		assert_eq!(Kitties::kitties(1).unwrap().list_price, None);
+		assert_noop!(Kitties::buy(Origin::signed(3), 1, 10), Error::<Test>::KittyNotForSale);
+
+		// Kitties in an auction cannot be leased
+		assert_ok!(Kitties::create(Origin::signed(3)));
+		assert_ok!(Kitties::lease(Origin::signed(3), 2, 2, 10, 20));
+		assert_ok!(Kitties::create_auction(Origin::signed(3), 2, 10, 10));
+		assert_noop!(
+			Kitties::accept_lease(Origin::signed(2), 2, 20),
+			Error::<Test>::KittyInAuction
+		);
+		assert_noop!(
+			Kitties::lease(Origin::signed(3), 2, 1, 10, 20),
+			Error::<Test>::KittyInAuction
+		);
+	});
+}
+
+#[test]
+fn name_kitty_and_set_metadata() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_ok!(Kitties::create(Origin::signed(2)));
+
+		assert_noop!(
+			Kitties::set_name(Origin::signed(2), 1, b"tom".to_vec()),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(Kitties::set_name(Origin::signed(1), 1, vec![]), Error::<Test>::InvalidName);
+		assert_noop!(
+			Kitties::set_name(Origin::signed(1), 1, b"tom the cat".to_vec()),
+			Error::<Test>::NameTooLong
+		);
+		assert_ok!(Kitties::set_name(Origin::signed(1), 1, b"tom".to_vec()));
+		System::assert_last_event(Event::Kitties(crate::Event::KittyNamed(1, 1, b"tom".to_vec())));
+		assert_eq!(Balances::reserved_balance(1), ReserveAmount::get() + NameDeposit::get());
+		assert_eq!(Kitties::kitty_by_name(name(b"tom")), Some(1));
+		assert_noop!(
+			Kitties::set_name(Origin::signed(2), 2, b"tom".to_vec()),
+			Error::<Test>::NameTaken
+		);
+
+		assert_noop!(
+			Kitties::set_metadata(Origin::signed(1), 1, vec![b'm'; 33]),
+			Error::<Test>::MetadataTooLong
+		);
+		assert_ok!(Kitties::set_metadata(Origin::signed(1), 1, b"ipfs://tom".to_vec()));
+		assert_eq!(Kitties::kitty_metadata(1).map(|m| m.to_vec()), Some(b"ipfs://tom".to_vec()));
+
+		// The kitty keeps its name and metadata, the deposit is released when it is renamed
+		assert_ok!(Kitties::transfer(Origin::signed(1), 2, 1));
+		assert_eq!(Kitties::kitty_name(1).map(|n| n.name.to_vec()), Some(b"tom".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), NameDeposit::get());
+		assert_noop!(Kitties::clear_name(Origin::signed(1), 1), Error::<Test>::NotOwner);
+		assert_ok!(Kitties::set_name(Origin::signed(2), 1, b"jerry".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 2 * ReserveAmount::get() + NameDeposit::get());
+		assert_eq!(Kitties::kitty_by_name(name(b"tom")), None);
+		assert_ok!(Kitties::set_name(Origin::signed(2), 2, b"tom".to_vec()));
+
+		// Offensive names are cleared by the force origin, slashing the deposit
+		assert_noop!(Kitties::force_clear_name(Origin::signed(2), 1), BadOrigin);
+		assert_ok!(Kitties::force_clear_name(Origin::root(), 1));
+		System::assert_last_event(Event::Kitties(crate::Event::KittyNameCleared(1)));
+		assert_eq!(Kitties::kitty_name(1), None);
+		assert_eq!(
+			Balances::free_balance(2) + Balances::reserved_balance(2),
+			100 - NameDeposit::get()
+		);
+		assert_noop!(Kitties::clear_name(Origin::signed(2), 1), Error::<Test>::NameNotSet);
+
+		// Burning a kitty frees its name
+		assert_ok!(Kitties::burn(Origin::signed(2), 2));
+		assert_eq!(Kitties::kitty_by_name(name(b"tom")), None);
+		assert_eq!(Balances::reserved_balance(2), ReserveAmount::get());
+	});
+}
+
+#[test]
+fn kitty_dna_is_revealed_after_delay() {
+	new_test_ext().execute_with(|| {
+		RevealDelay::set(3);
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		// The index and the deposit are taken at once, the kitty does not exist yet
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		System::assert_last_event(Event::Kitties(crate::Event::KittyPending(1, 1, 84)));
+		assert_eq!(Kitties::kitties_count(), Some(1));
+		assert!(Kitties::kitties(1).is_none());
+		assert_eq!(Kitties::owner(1), None);
+		assert_eq!(Balances::reserved_balance(1), ReserveAmount::get());
+		assert_eq!(Kitties::owned_kitties_count(1), 1);
+		assert_noop!(Kitties::transfer(Origin::signed(1), 2, 1), Error::<Test>::NotOwner);
+
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		assert_noop!(Kitties::create(Origin::signed(1)), Error::<Test>::TooManyReveals);
+
+		System::set_block_number(84);
+		Kitties::on_initialize(84);
+		System::assert_last_event(Event::Kitties(crate::Event::KittyCreated(1, 2)));
+		assert!(Kitties::pending_kitties(1).is_none());
+		assert!(Kitties::reveals_at(84).is_empty());
+		assert_eq!(Kitties::owner(1), Some(1));
+		assert_eq!(Kitties::owned_kitties_count(1), 2);
+		assert_ne!(Kitties::kitties(1).unwrap().dna, Kitties::kitties(2).unwrap().dna);
+
+		// Bred kitties are revealed from the genes of their parents
+		set_gender(1, Gender::Female);
+		set_gender(2, Gender::Male);
+		let dna_1 = Kitties::kitties(1).unwrap().dna;
+		let dna_2 = Kitties::kitties(2).unwrap().dna;
+		assert_ok!(Kitties::breed(Origin::signed(1), 1, 2));
+		System::assert_last_event(Event::Kitties(crate::Event::KittyPending(1, 3, 87)));
+		assert_eq!(Kitties::pending_kitties(3).unwrap().genes, Some((dna_1, dna_2)));
+
+		System::set_block_number(87);
+		Kitties::on_initialize(87);
+		let kitty = Kitties::kitties(3).unwrap();
+		assert_eq!(Kitties::owner(3), Some(1));
+		assert_eq!(kitty.parents, Some((1, 2)));
+		assert_eq!(kitty.generation, 1);
+		for i in 0..16 {
+			assert_eq!(kitty.dna[i] & !(dna_1[i] | dna_2[i]), 0);
+		}
+		assert_eq!(Kitties::owned_kitties_count(1), 3);
+	});
+}
+
+#[test]
+fn pending_kitties_count_towards_max_owned() {
+	use frame_support::traits::tokens::nonfungibles::Mutate;
+
+	new_test_ext().execute_with(|| {
+		RevealDelay::set(3);
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+
+		// Spread over several blocks to stay below `MaxRevealsPerBlock`
+		for n in 0..MaxKittiesOwned::get() as u64 {
+			System::set_block_number(81 + n);
+			assert_ok!(Kitties::create(Origin::signed(1)));
+		}
+		assert_eq!(Kitties::owned_kitties_count(1), MaxKittiesOwned::get());
+
+		System::set_block_number(81 + MaxKittiesOwned::get() as u64);
+		assert_noop!(Kitties::create(Origin::signed(1)), Error::<Test>::ExceedMaxKittiesOwned);
+
+		// Minted kitties go through the same delayed reveal
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+		let next = Kitties::next_kitty_id().unwrap();
+		assert_noop!(
+			<Kitties as Mutate<u64>>::mint_into(&(), &next, &1),
+			Error::<Test>::ExceedMaxKittiesOwned
+		);
+		assert_ok!(<Kitties as Mutate<u64>>::mint_into(&(), &next, &2));
+		assert_eq!(Kitties::owner(next), None);
+		assert!(Kitties::pending_kitties(next).is_some());
+		assert_eq!(Kitties::owned_kitties_count(2), 1);
 	});
 }
@@ -0,0 +1,66 @@
+//! Deterministic battles between kitties, from the traits encoded in their DNA.
+//!
+//! Besides the visible traits of `KittyTraits`, byte 7 of the DNA adds to the attack, byte 8 to
+//! the defense and byte 9 to the speed.
+
+use codec::{Decode, Encode};
+use pallet_kitties::KittyTraits;
+use sp_runtime::RuntimeDebug;
+
+/// Health of both kitties at the start of a battle.
+pub const START_HEALTH: u16 = 100;
+/// Battles still undecided after this many rounds are won by the healthier kitty.
+pub const MAX_ROUNDS: u8 = 20;
+
+/// The battle stats of a kitty, as derived from its DNA.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BattleStats {
+	pub attack: u16,
+	pub defense: u16,
+	/// The faster kitty strikes first in every round.
+	pub speed: u16,
+}
+
+impl BattleStats {
+	pub fn from_dna(dna: &[u8; 16]) -> Self {
+		let traits = KittyTraits::from_dna(dna);
+		// Rarer kitties are better at everything
+		let bonus = traits.rarity_score as u16 * 2;
+
+		BattleStats {
+			attack: 10 + (dna[7] % 32) as u16 + traits.body_color as u16 + bonus,
+			defense: 5 + (dna[8] % 32) as u16 + traits.pattern as u16 + bonus,
+			speed: (dna[9] % 32) as u16 + traits.eye_shape as u16 + bonus,
+		}
+	}
+
+	// The health taken from `defender` by a single strike
+	fn damage_to(&self, defender: &BattleStats) -> u16 {
+		self.attack.saturating_sub(defender.defense / 2).max(1)
+	}
+}
+
+/// Whether the kitty with stats `a` beats the one with stats `b`. They strike in turn, the
+/// fastest first, until one runs out of health or `MAX_ROUNDS` are fought. `a` wins all ties.
+pub fn first_wins(a: &BattleStats, b: &BattleStats) -> bool {
+	let (mut health_a, mut health_b) = (START_HEALTH, START_HEALTH);
+	let turns = if a.speed >= b.speed { [true, false] } else { [false, true] };
+
+	for _ in 0..MAX_ROUNDS {
+		for &a_strikes in turns.iter() {
+			if a_strikes {
+				health_b = health_b.saturating_sub(a.damage_to(b));
+				if health_b == 0 {
+					return true
+				}
+			} else {
+				health_a = health_a.saturating_sub(b.damage_to(a));
+				if health_a == 0 {
+					return false
+				}
+			}
+		}
+	}
+
+	health_a >= health_b
+}
@@ -0,0 +1,149 @@
+//! Benchmarking setup for pallet-kitty-battles
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as KittyBattles;
+use crate::pallet::BalanceOf;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get, Hooks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Zero};
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 4u32.into());
+	who
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 4u32.into());
+	caller
+}
+
+// Create a kitty for `owner` and return its index, revealing it at once if needed
+fn create_kitty<T: Config>(owner: &T::AccountId) -> T::KittyIndex {
+	pallet_kitties::Pallet::<T>::create(RawOrigin::Signed(owner.clone()).into()).unwrap();
+	let delay = <T as pallet_kitties::Config>::RevealDelay::get();
+	if !delay.is_zero() {
+		let reveal_at = frame_system::Pallet::<T>::block_number() + delay;
+		pallet_kitties::Pallet::<T>::on_initialize(reveal_at);
+	}
+	pallet_kitties::Pallet::<T>::kitties_count().unwrap()
+}
+
+// Schedule a tournament open to `MaxEntrants` kitties and enter `n` of them
+fn create_tournament<T: Config>(n: u32) -> (TournamentIndex, T::BlockNumber) {
+	let organizer = funded_account::<T>("organizer", 0);
+	let start = frame_system::Pallet::<T>::block_number() + T::MaxTournamentDelay::get();
+	let tournament_id = KittyBattles::<T>::tournaments_count();
+	KittyBattles::<T>::create_tournament(
+		RawOrigin::Signed(organizer).into(),
+		1_000u32.into(),
+		T::MaxEntrants::get(),
+		start,
+	)
+	.unwrap();
+	for i in 0..n {
+		let entrant = funded_account::<T>("entrant", i);
+		let kitty_id = create_kitty::<T>(&entrant);
+		KittyBattles::<T>::enter_tournament(
+			RawOrigin::Signed(entrant).into(),
+			tournament_id,
+			kitty_id,
+		)
+		.unwrap();
+	}
+	(tournament_id, start)
+}
+
+benchmarks! {
+	challenge {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		let opponent = funded_account::<T>("opponent", 0);
+		let opponent_id = create_kitty::<T>(&opponent);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, opponent_id)
+	verify {
+		assert_eq!(KittyBattles::<T>::challenge(opponent_id, kitty_id), Some(caller));
+	}
+
+	cancel_challenge {
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		let opponent = funded_account::<T>("opponent", 0);
+		let opponent_id = create_kitty::<T>(&opponent);
+		KittyBattles::<T>::challenge(RawOrigin::Signed(caller.clone()).into(), kitty_id, opponent_id)?;
+	}: _(RawOrigin::Signed(caller), kitty_id, opponent_id)
+	verify {
+		assert_eq!(KittyBattles::<T>::challenge(opponent_id, kitty_id), None);
+	}
+
+	accept_challenge {
+		let challenger = funded_account::<T>("challenger", 0);
+		let challenger_id = create_kitty::<T>(&challenger);
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+		KittyBattles::<T>::challenge(RawOrigin::Signed(challenger).into(), challenger_id, kitty_id)?;
+	}: _(RawOrigin::Signed(caller), kitty_id, challenger_id)
+	verify {
+		let record = KittyBattles::<T>::record(kitty_id);
+		assert_eq!(record.wins + record.losses, 1);
+	}
+
+	// Other tournaments start in the same block
+	create_tournament {
+		let start = frame_system::Pallet::<T>::block_number() + T::MaxTournamentDelay::get();
+		for _ in 1..T::MaxTournamentsPerBlock::get() {
+			create_tournament::<T>(0);
+		}
+		let caller = funded_caller::<T>();
+		let tournament_id = KittyBattles::<T>::tournaments_count();
+	}: _(RawOrigin::Signed(caller), 1_000u32.into(), T::MaxEntrants::get(), start)
+	verify {
+		assert!(KittyBattles::<T>::tournaments(tournament_id).is_some());
+	}
+
+	fund_tournament {
+		let caller = funded_caller::<T>();
+		let start = frame_system::Pallet::<T>::block_number() + T::MaxTournamentDelay::get();
+		let tournament_id = KittyBattles::<T>::tournaments_count();
+		KittyBattles::<T>::create_tournament(
+			RawOrigin::Signed(caller.clone()).into(),
+			1_000u32.into(),
+			T::MaxEntrants::get(),
+			start,
+		)?;
+		let amount = T::Currency::minimum_balance();
+	}: _(RawOrigin::Signed(caller), tournament_id, amount)
+	verify {
+		assert_eq!(KittyBattles::<T>::prize_pool(tournament_id), amount);
+	}
+
+	// The tournament has room for a single more kitty
+	enter_tournament {
+		let (tournament_id, _) = create_tournament::<T>(T::MaxEntrants::get() - 1);
+		let caller = funded_caller::<T>();
+		let kitty_id = create_kitty::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), tournament_id, kitty_id)
+	verify {
+		let tournament = KittyBattles::<T>::tournaments(tournament_id).unwrap();
+		assert_eq!(tournament.entrants.len() as u32, T::MaxEntrants::get());
+	}
+
+	// Fighting a tournament between `e` kitties
+	run_tournament {
+		let e in 0 .. T::MaxEntrants::get();
+		let (tournament_id, start) = create_tournament::<T>(e);
+	}: {
+		KittyBattles::<T>::on_initialize(start);
+	}
+	verify {
+		assert!(KittyBattles::<T>::tournaments(tournament_id).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(KittyBattles, crate::mock::new_test_ext(), crate::mock::Test);
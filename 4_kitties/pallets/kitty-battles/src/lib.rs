@@ -0,0 +1,526 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+pub use battle::BattleStats;
+pub use pallet::*;
+
+pub mod battle;
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+
+	use crate::battle::{self, BattleStats};
+	pub use crate::weights::WeightInfo;
+	use codec::{Decode, Encode};
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement, ReservableCurrency},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_kitties::{Auctions, Fractions, Leases, PendingKitties};
+	use sp_runtime::{
+		traits::{AccountIdConversion, Zero},
+		RuntimeDebug,
+	};
+	use sp_std::vec::Vec;
+
+	pub(crate) type BalanceOf<T> = <<T as pallet_kitties::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	pub type TournamentIndex = u32;
+
+	/// The battles won and lost by a kitty.
+	#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+	pub struct Record {
+		pub wins: u32,
+		pub losses: u32,
+	}
+
+	/// A single elimination tournament, fought at the start of block `start`.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct Tournament<AccountId, Balance, BlockNumber, KittyIndex> {
+		pub organizer: AccountId,
+		/// Reserved from the organizer until the tournament is fought.
+		pub deposit: Balance,
+		/// Paid by every entrant into the prize pool, which the owner of the winning kitty takes.
+		pub entry_fee: Balance,
+		pub start: BlockNumber,
+		pub max_entrants: u32,
+		/// The kitties entered and the accounts who entered them, in order of entry.
+		pub entrants: Vec<(KittyIndex, AccountId)>,
+	}
+
+	type TournamentOf<T> = Tournament<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as pallet_kitties::Config>::KittyIndex,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_kitties::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The pallet id, from which the prize pool account of every tournament is derived.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Reserved from the organizer of a tournament until it is fought.
+		#[pallet::constant]
+		type TournamentDeposit: Get<BalanceOf<Self>>;
+		/// The maximum number of kitties entering a tournament.
+		#[pallet::constant]
+		type MaxEntrants: Get<u32>;
+		/// The maximum number of tournaments starting in the same block. All of them, each with
+		/// `MaxEntrants` kitties, must fit in a block.
+		#[pallet::constant]
+		type MaxTournamentsPerBlock: Get<u32>;
+		/// The latest a tournament can start, in blocks after its creation.
+		#[pallet::constant]
+		type MaxTournamentDelay: Get<Self::BlockNumber>;
+		/// Information on runtime weights.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	#[pallet::getter(fn record)]
+	pub type Records<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::KittyIndex, Record, ValueQuery>;
+
+	/// The owners challenging a kitty, by challenged kitty and challenger kitty.
+	#[pallet::storage]
+	#[pallet::getter(fn challenge)]
+	pub type Challenges<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::KittyIndex,
+		Blake2_128Concat,
+		T::KittyIndex,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournaments_count)]
+	pub type TournamentsCount<T: Config> = StorageValue<_, TournamentIndex, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournaments)]
+	pub type Tournaments<T: Config> =
+		StorageMap<_, Blake2_128Concat, TournamentIndex, TournamentOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn tournaments_starting_at)]
+	pub type TournamentsStartingAt<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<TournamentIndex>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An owner challenged the kitty of another owner. [challenger, kitty_id, opponent_id]
+		ChallengeIssued(T::AccountId, T::KittyIndex, T::KittyIndex),
+		/// A challenge was withdrawn by its challenger. [kitty_id, opponent_id]
+		ChallengeCancelled(T::KittyIndex, T::KittyIndex),
+		/// A challenge was accepted and fought. [winner_id, loser_id]
+		BattleFought(T::KittyIndex, T::KittyIndex),
+		/// A tournament was scheduled. [organizer, tournament_id, entry_fee, start]
+		TournamentCreated(T::AccountId, TournamentIndex, BalanceOf<T>, T::BlockNumber),
+		/// An owner entered a kitty in a tournament. [owner, tournament_id, kitty_id]
+		TournamentEntered(T::AccountId, TournamentIndex, T::KittyIndex),
+		/// The organizer added to the prize pool of a tournament. [tournament_id, amount]
+		TournamentFunded(TournamentIndex, BalanceOf<T>),
+		/// The owner of the last kitty standing took the prize pool.
+		/// [tournament_id, kitty_id, owner, prize]
+		TournamentWon(TournamentIndex, T::KittyIndex, T::AccountId, BalanceOf<T>),
+		/// Fewer than two kitties could fight, the entry fees and the funding of the organizer
+		/// were given back. [tournament_id]
+		TournamentCancelled(TournamentIndex),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		InvalidKittyIndex,
+		NotOwner,
+		SameOwner,
+		ChallengeNotFound,
+		TournamentNotFound,
+		TournamentStarted,
+		TournamentFull,
+		AlreadyEntered,
+		InvalidStart,
+		InvalidMaxEntrants,
+		TooManyTournaments,
+		InsufficientBalance,
+		AmountTooLow,
+		KittyUnavailable,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+			for tournament_id in TournamentsStartingAt::<T>::take(n) {
+				if let Some(tournament) = Tournaments::<T>::take(tournament_id) {
+					let entrants = tournament.entrants.len() as u32;
+					Self::run_tournament(tournament_id, tournament);
+					weight =
+						weight.saturating_add(<T as Config>::WeightInfo::run_tournament(entrants));
+				}
+			}
+			weight
+		}
+
+		fn integrity_test() {
+			let max_weight = <T as Config>::WeightInfo::run_tournament(T::MaxEntrants::get())
+				.saturating_mul(T::MaxTournamentsPerBlock::get() as Weight);
+			assert!(
+				max_weight <= T::BlockWeights::get().max_block,
+				"the tournaments starting in a block do not fit in it"
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Challenge `opponent_id`, a kitty of another owner, with the caller's `kitty_id`.
+		#[pallet::weight(<T as Config>::WeightInfo::challenge())]
+		pub fn challenge(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			opponent_id: T::KittyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				Self::is_free(kitty_id) && Self::is_free(opponent_id),
+				Error::<T>::KittyUnavailable
+			);
+			ensure!(Some(who.clone()) == Self::owner_of(kitty_id), Error::<T>::NotOwner);
+			let opponent = Self::owner_of(opponent_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+			ensure!(who != opponent, Error::<T>::SameOwner);
+
+			Challenges::<T>::insert(opponent_id, kitty_id, &who);
+
+			Self::deposit_event(Event::ChallengeIssued(who, kitty_id, opponent_id));
+
+			Ok(())
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_challenge())]
+		pub fn cancel_challenge(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			opponent_id: T::KittyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				Self::challenge(opponent_id, kitty_id) == Some(who),
+				Error::<T>::ChallengeNotFound
+			);
+
+			Challenges::<T>::remove(opponent_id, kitty_id);
+
+			Self::deposit_event(Event::ChallengeCancelled(kitty_id, opponent_id));
+
+			Ok(())
+		}
+
+		/// Fight the challenge of `challenger_id` against the caller's `kitty_id`, which wins
+		/// if both kitties are equally strong.
+		#[pallet::weight(<T as Config>::WeightInfo::accept_challenge())]
+		pub fn accept_challenge(
+			origin: OriginFor<T>,
+			kitty_id: T::KittyIndex,
+			challenger_id: T::KittyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				Self::is_free(kitty_id) && Self::is_free(challenger_id),
+				Error::<T>::KittyUnavailable
+			);
+			ensure!(Some(who.clone()) == Self::owner_of(kitty_id), Error::<T>::NotOwner);
+			// The challenge lapses when the challenger kitty changes hands
+			let challenger = Self::challenge(kitty_id, challenger_id)
+				.filter(|challenger| Some(challenger.clone()) == Self::owner_of(challenger_id))
+				.ok_or(Error::<T>::ChallengeNotFound)?;
+			ensure!(who != challenger, Error::<T>::SameOwner);
+
+			let stats = Self::stats_of(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+			let challenger_stats =
+				Self::stats_of(challenger_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+
+			Challenges::<T>::remove(kitty_id, challenger_id);
+
+			let (winner_id, loser_id) = if battle::first_wins(&stats, &challenger_stats) {
+				(kitty_id, challenger_id)
+			} else {
+				(challenger_id, kitty_id)
+			};
+			Self::record_battle(winner_id, loser_id);
+
+			Self::deposit_event(Event::BattleFought(winner_id, loser_id));
+
+			Ok(())
+		}
+
+		/// Schedule a tournament starting at block `start`, open to `max_entrants` kitties
+		/// paying `entry_fee` each. The organizer reserves `TournamentDeposit` and pays for
+		/// fighting the tournament upfront.
+		#[pallet::weight({
+			let e = (*max_entrants).min(T::MaxEntrants::get());
+			<T as Config>::WeightInfo::create_tournament()
+				.saturating_add(<T as Config>::WeightInfo::run_tournament(e))
+		})]
+		pub fn create_tournament(
+			origin: OriginFor<T>,
+			entry_fee: BalanceOf<T>,
+			max_entrants: u32,
+			start: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				start > now && start - now <= T::MaxTournamentDelay::get(),
+				Error::<T>::InvalidStart
+			);
+			ensure!(
+				max_entrants >= 2 && max_entrants <= T::MaxEntrants::get(),
+				Error::<T>::InvalidMaxEntrants
+			);
+			// Fees are paid into an account that may not exist yet
+			ensure!(
+				entry_fee.is_zero() || entry_fee >= T::Currency::minimum_balance(),
+				Error::<T>::AmountTooLow
+			);
+
+			let tournament_id = Self::tournaments_count();
+			let next_id = tournament_id.checked_add(1).ok_or(Error::<T>::TooManyTournaments)?;
+			ensure!(
+				(TournamentsStartingAt::<T>::decode_len(start).unwrap_or(0) as u32) <
+					T::MaxTournamentsPerBlock::get(),
+				Error::<T>::TooManyTournaments
+			);
+
+			let deposit = T::TournamentDeposit::get();
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+
+			TournamentsStartingAt::<T>::append(start, tournament_id);
+
+			TournamentsCount::<T>::put(next_id);
+			Tournaments::<T>::insert(
+				tournament_id,
+				Tournament {
+					organizer: who.clone(),
+					deposit,
+					entry_fee,
+					start,
+					max_entrants,
+					entrants: Vec::new(),
+				},
+			);
+
+			Self::deposit_event(Event::TournamentCreated(who, tournament_id, entry_fee, start));
+
+			Ok(())
+		}
+
+		/// Add `amount` to the prize pool of a tournament of the caller.
+		#[pallet::weight(<T as Config>::WeightInfo::fund_tournament())]
+		pub fn fund_tournament(
+			origin: OriginFor<T>,
+			tournament_id: TournamentIndex,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let tournament =
+				Self::tournaments(tournament_id).ok_or(Error::<T>::TournamentNotFound)?;
+
+			ensure!(who == tournament.organizer, Error::<T>::NotOwner);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < tournament.start,
+				Error::<T>::TournamentStarted
+			);
+			// Keeps the pool above the existential deposit while entry fees are refunded
+			ensure!(amount >= T::Currency::minimum_balance(), Error::<T>::AmountTooLow);
+
+			T::Currency::transfer(
+				&who,
+				&Self::pot_account(tournament_id),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)
+			.map_err(|_| Error::<T>::InsufficientBalance)?;
+
+			Self::deposit_event(Event::TournamentFunded(tournament_id, amount));
+
+			Ok(())
+		}
+
+		/// Enter the caller's kitty in a tournament, paying the entry fee into its prize pool.
+		/// A kitty no longer owned by the caller, or no longer free, when the tournament starts is
+		/// out, and its fee goes to the winner.
+		#[pallet::weight(<T as Config>::WeightInfo::enter_tournament())]
+		pub fn enter_tournament(
+			origin: OriginFor<T>,
+			tournament_id: TournamentIndex,
+			kitty_id: T::KittyIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_free(kitty_id), Error::<T>::KittyUnavailable);
+			ensure!(Some(who.clone()) == Self::owner_of(kitty_id), Error::<T>::NotOwner);
+
+			Tournaments::<T>::try_mutate(tournament_id, |tournament| -> DispatchResult {
+				let tournament = tournament.as_mut().ok_or(Error::<T>::TournamentNotFound)?;
+
+				ensure!(
+					<frame_system::Pallet<T>>::block_number() < tournament.start,
+					Error::<T>::TournamentStarted
+				);
+				ensure!(
+					(tournament.entrants.len() as u32) < tournament.max_entrants,
+					Error::<T>::TournamentFull
+				);
+				ensure!(
+					!tournament.entrants.iter().any(|(id, _)| *id == kitty_id),
+					Error::<T>::AlreadyEntered
+				);
+
+				T::Currency::transfer(
+					&who,
+					&Self::pot_account(tournament_id),
+					tournament.entry_fee,
+					ExistenceRequirement::KeepAlive,
+				)
+				.map_err(|_| Error::<T>::InsufficientBalance)?;
+				tournament.entrants.push((kitty_id, who.clone()));
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::TournamentEntered(who, tournament_id, kitty_id));
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The battle stats of a kitty.
+		pub fn stats_of(kitty_id: T::KittyIndex) -> Option<BattleStats> {
+			pallet_kitties::Pallet::<T>::kitties(kitty_id)
+				.map(|kitty| BattleStats::from_dna(&kitty.dna))
+		}
+
+		/// The account holding the prize pool of a tournament.
+		pub fn pot_account(tournament_id: TournamentIndex) -> T::AccountId {
+			<T as Config>::PalletId::get().into_sub_account(tournament_id)
+		}
+
+		/// The prize pool of a tournament: the entry fees paid so far and the funding of the
+		/// organizer.
+		pub fn prize_pool(tournament_id: TournamentIndex) -> BalanceOf<T> {
+			T::Currency::free_balance(&Self::pot_account(tournament_id))
+		}
+
+		fn owner_of(kitty_id: T::KittyIndex) -> Option<T::AccountId> {
+			pallet_kitties::Pallet::<T>::owner(kitty_id)
+		}
+
+		// Kitties in an auction, leased, fractionalized or waiting for their DNA cannot fight
+		fn is_free(kitty_id: T::KittyIndex) -> bool {
+			!Auctions::<T>::contains_key(kitty_id) &&
+				!Leases::<T>::contains_key(kitty_id) &&
+				!Fractions::<T>::contains_key(kitty_id) &&
+				!PendingKitties::<T>::contains_key(kitty_id)
+		}
+
+		fn record_battle(winner_id: T::KittyIndex, loser_id: T::KittyIndex) {
+			Records::<T>::mutate(winner_id, |record| record.wins = record.wins.saturating_add(1));
+			Records::<T>::mutate(loser_id, |record| {
+				record.losses = record.losses.saturating_add(1)
+			});
+		}
+
+		// Pair the kitties in order of entry, round after round, until one is left. The last
+		// kitty of a round with an odd number of kitties goes straight to the next one.
+		fn run_tournament(tournament_id: TournamentIndex, tournament: TournamentOf<T>) {
+			let pot = Self::pot_account(tournament_id);
+			T::Currency::unreserve(&tournament.organizer, tournament.deposit);
+
+			let mut bracket: Vec<(T::KittyIndex, T::AccountId, BattleStats)> = tournament
+				.entrants
+				.iter()
+				.filter(|(kitty_id, who)| {
+					Self::owner_of(*kitty_id).as_ref() == Some(who) && Self::is_free(*kitty_id)
+				})
+				.filter_map(|(kitty_id, who)| {
+					Some((*kitty_id, who.clone(), Self::stats_of(*kitty_id)?))
+				})
+				.collect();
+
+			if bracket.len() < 2 {
+				// Every payment into the pool is at least the existential deposit, so the pool
+				// cannot be reaped before everyone is paid back
+				for (_, who) in &tournament.entrants {
+					let _ = T::Currency::transfer(
+						&pot,
+						who,
+						tournament.entry_fee,
+						ExistenceRequirement::AllowDeath,
+					);
+				}
+				let _ = T::Currency::transfer(
+					&pot,
+					&tournament.organizer,
+					T::Currency::free_balance(&pot),
+					ExistenceRequirement::AllowDeath,
+				);
+				Self::deposit_event(Event::TournamentCancelled(tournament_id));
+				return
+			}
+
+			while bracket.len() > 1 {
+				let mut next_round = Vec::with_capacity((bracket.len() + 1) / 2);
+				for pair in bracket.chunks(2) {
+					match pair {
+						[a, b] => {
+							let (winner, loser) =
+								if battle::first_wins(&a.2, &b.2) { (a, b) } else { (b, a) };
+							Self::record_battle(winner.0, loser.0);
+							next_round.push(winner.clone());
+						},
+						_ => next_round.extend_from_slice(pair),
+					}
+				}
+				bracket = next_round;
+			}
+
+			let (kitty_id, winner, _) = bracket.swap_remove(0);
+
+			let prize = T::Currency::free_balance(&pot);
+			let prize =
+				T::Currency::transfer(&pot, &winner, prize, ExistenceRequirement::AllowDeath)
+					.map_or(Zero::zero(), |_| prize);
+
+			Self::deposit_event(Event::TournamentWon(tournament_id, kitty_id, winner, prize));
+		}
+	}
+}
@@ -0,0 +1,149 @@
+use crate as pallet_kitty_battles;
+use frame_support::{parameter_types, PalletId};
+use frame_support_test::TestRandomness;
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type Balance = u64;
+pub type KittyIndex = u32;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Kitties: pallet_kitties::{Pallet, Call, Storage, Event<T>, Config<T>},
+		KittyBattles: pallet_kitty_battles::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::AllowAll;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const ReserveAmount: u64 = 0;
+	pub const MaxKittiesOwned: u32 = 5;
+	pub const AllowInbreeding: bool = false;
+	pub const BreedingCooldown: u64 = 5;
+	pub const BreedingFee: u64 = 0;
+	pub const MaxAuctionDuration: u64 = 20;
+	pub const MaxAuctionsPerBlock: u32 = 2;
+	pub const MarketplaceFee: Perbill = Perbill::from_percent(10);
+	pub const TreasuryAccount: u64 = 100;
+	pub const MarketplaceEnabled: bool = true;
+	pub const MaxLeaseDuration: u64 = 20;
+	pub const MaxLeasesPerBlock: u32 = 2;
+	pub const KittiesPalletId: PalletId = PalletId(*b"py/kitty");
+	pub const NameDeposit: u64 = 5;
+	pub const MaxNameLength: u32 = 8;
+	pub const MaxMetadataLength: u32 = 32;
+	pub static RevealDelay: u64 = 0;
+	pub const MaxRevealsPerBlock: u32 = 2;
+}
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_kitties::Config for Test {
+	type Event = Event;
+	type Randomness = TestRandomness<Self>;
+	type KittyIndex = KittyIndex;
+	type Balance = Balance;
+	type Currency = Balances;
+	type ReserveAmount = ReserveAmount;
+	type AllowInbreeding = AllowInbreeding;
+	type BreedingCooldown = BreedingCooldown;
+	type BreedingFee = BreedingFee;
+	type MaxAuctionDuration = MaxAuctionDuration;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MarketplaceEnabled = MarketplaceEnabled;
+	type MarketplaceFee = MarketplaceFee;
+	type TreasuryAccount = TreasuryAccount;
+	type MaxKittiesOwned = MaxKittiesOwned;
+	type MaxLeaseDuration = MaxLeaseDuration;
+	type MaxLeasesPerBlock = MaxLeasesPerBlock;
+	type PalletId = KittiesPalletId;
+	type NameDeposit = NameDeposit;
+	type MaxNameLength = MaxNameLength;
+	type MaxMetadataLength = MaxMetadataLength;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type RevealDelay = RevealDelay;
+	type MaxRevealsPerBlock = MaxRevealsPerBlock;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const KittyBattlesPalletId: PalletId = PalletId(*b"py/battl");
+	pub const TournamentDeposit: u64 = 5;
+	pub const MaxEntrants: u32 = 4;
+	pub const MaxTournamentsPerBlock: u32 = 2;
+	pub const MaxTournamentDelay: u64 = 20;
+}
+
+impl pallet_kitty_battles::Config for Test {
+	type Event = Event;
+	type PalletId = KittyBattlesPalletId;
+	type TournamentDeposit = TournamentDeposit;
+	type MaxEntrants = MaxEntrants;
+	type MaxTournamentsPerBlock = MaxTournamentsPerBlock;
+	type MaxTournamentDelay = MaxTournamentDelay;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	// set the block number so that random_seed() works correctly
+	ext.execute_with(|| System::set_block_number(81));
+	ext
+}
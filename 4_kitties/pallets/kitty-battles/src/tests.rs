@@ -0,0 +1,296 @@
+use crate::{battle, mock::*, BattleStats, Error, Record};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+
+const STRONG: [u8; 16] = [0xff; 16];
+const AVERAGE: [u8; 16] = [0x01; 16];
+const WEAK: [u8; 16] = [0x80; 16];
+
+// Create a kitty for `owner` with the given DNA, which is otherwise fixed by the test randomness
+fn create_kitty(owner: u64, dna: [u8; 16]) -> KittyIndex {
+	assert_ok!(Kitties::create(Origin::signed(owner)));
+	let kitty_id = Kitties::kitties_count().unwrap();
+	pallet_kitties::Kitties::<Test>::mutate(kitty_id, |kitty| {
+		if let Some(k) = kitty {
+			k.dna = dna;
+		}
+	});
+	kitty_id
+}
+
+fn record(wins: u32, losses: u32) -> Record {
+	Record { wins, losses }
+}
+
+#[test]
+fn battles_are_deterministic() {
+	let strong = BattleStats::from_dna(&STRONG);
+	let average = BattleStats::from_dna(&AVERAGE);
+	let weak = BattleStats::from_dna(&WEAK);
+
+	assert!(battle::first_wins(&strong, &average));
+	assert!(!battle::first_wins(&average, &strong));
+	assert!(battle::first_wins(&average, &weak));
+	assert!(!battle::first_wins(&weak, &average));
+
+	// Ties go to the first kitty
+	assert!(battle::first_wins(&weak, &weak));
+}
+
+#[test]
+fn challenge_and_fight() {
+	new_test_ext().execute_with(|| {
+		let kitty_1 = create_kitty(1, STRONG);
+		let kitty_2 = create_kitty(2, AVERAGE);
+		let kitty_3 = create_kitty(1, WEAK);
+
+		assert_noop!(
+			KittyBattles::challenge(Origin::signed(2), kitty_1, kitty_2),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittyBattles::challenge(Origin::signed(1), kitty_1, 9),
+			Error::<Test>::InvalidKittyIndex
+		);
+		assert_noop!(
+			KittyBattles::challenge(Origin::signed(1), kitty_1, kitty_3),
+			Error::<Test>::SameOwner
+		);
+
+		assert_ok!(KittyBattles::challenge(Origin::signed(1), kitty_3, kitty_2));
+		System::assert_last_event(Event::KittyBattles(crate::Event::ChallengeIssued(
+			1, kitty_3, kitty_2,
+		)));
+		assert_noop!(
+			KittyBattles::cancel_challenge(Origin::signed(2), kitty_3, kitty_2),
+			Error::<Test>::ChallengeNotFound
+		);
+		assert_ok!(KittyBattles::cancel_challenge(Origin::signed(1), kitty_3, kitty_2));
+		assert_eq!(KittyBattles::challenge(kitty_2, kitty_3), None);
+
+		assert_ok!(KittyBattles::challenge(Origin::signed(1), kitty_1, kitty_2));
+		assert_noop!(
+			KittyBattles::accept_challenge(Origin::signed(1), kitty_2, kitty_1),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittyBattles::accept_challenge(Origin::signed(2), kitty_2, kitty_3),
+			Error::<Test>::ChallengeNotFound
+		);
+		assert_ok!(KittyBattles::accept_challenge(Origin::signed(2), kitty_2, kitty_1));
+		System::assert_last_event(Event::KittyBattles(crate::Event::BattleFought(
+			kitty_1, kitty_2,
+		)));
+		assert_eq!(KittyBattles::record(kitty_1), record(1, 0));
+		assert_eq!(KittyBattles::record(kitty_2), record(0, 1));
+		assert_eq!(KittyBattles::challenge(kitty_2, kitty_1), None);
+
+		// A challenge lapses when the challenger kitty changes hands
+		assert_ok!(KittyBattles::challenge(Origin::signed(1), kitty_3, kitty_2));
+		assert_ok!(Kitties::transfer(Origin::signed(1), 3, kitty_3));
+		assert_noop!(
+			KittyBattles::accept_challenge(Origin::signed(2), kitty_2, kitty_3),
+			Error::<Test>::ChallengeNotFound
+		);
+	});
+}
+
+#[test]
+fn tournament_winner_takes_the_prize_pool() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=5 {
+			let _ = Balances::set_balance(Origin::root(), who, 100, 0);
+		}
+		let kitty_1 = create_kitty(1, AVERAGE);
+		let kitty_2 = create_kitty(2, WEAK);
+		let kitty_3 = create_kitty(3, STRONG);
+		let kitty_4 = create_kitty(4, WEAK);
+
+		assert_noop!(
+			KittyBattles::create_tournament(Origin::signed(5), 10, 3, 81),
+			Error::<Test>::InvalidStart
+		);
+		assert_noop!(
+			KittyBattles::create_tournament(
+				Origin::signed(5),
+				10,
+				3,
+				81 + MaxTournamentDelay::get() + 1
+			),
+			Error::<Test>::InvalidStart
+		);
+		assert_noop!(
+			KittyBattles::create_tournament(Origin::signed(5), 10, MaxEntrants::get() + 1, 90),
+			Error::<Test>::InvalidMaxEntrants
+		);
+		assert_ok!(KittyBattles::create_tournament(Origin::signed(5), 10, 3, 90));
+		System::assert_last_event(Event::KittyBattles(crate::Event::TournamentCreated(
+			5, 0, 10, 90,
+		)));
+		assert_eq!(Balances::reserved_balance(5), TournamentDeposit::get());
+
+		// Only the organizer funds the pool
+		assert_noop!(
+			KittyBattles::fund_tournament(Origin::signed(1), 0, 20),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittyBattles::fund_tournament(Origin::signed(5), 0, 0),
+			Error::<Test>::AmountTooLow
+		);
+		assert_ok!(KittyBattles::fund_tournament(Origin::signed(5), 0, 20));
+		System::assert_last_event(Event::KittyBattles(crate::Event::TournamentFunded(0, 20)));
+
+		assert_noop!(
+			KittyBattles::enter_tournament(Origin::signed(2), 0, kitty_1),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittyBattles::enter_tournament(Origin::signed(1), 1, kitty_1),
+			Error::<Test>::TournamentNotFound
+		);
+		assert_ok!(KittyBattles::enter_tournament(Origin::signed(1), 0, kitty_1));
+		assert_noop!(
+			KittyBattles::enter_tournament(Origin::signed(1), 0, kitty_1),
+			Error::<Test>::AlreadyEntered
+		);
+		assert_ok!(KittyBattles::enter_tournament(Origin::signed(2), 0, kitty_2));
+		assert_ok!(KittyBattles::enter_tournament(Origin::signed(3), 0, kitty_3));
+		assert_noop!(
+			KittyBattles::enter_tournament(Origin::signed(4), 0, kitty_4),
+			Error::<Test>::TournamentFull
+		);
+		// The entry fees are in the pool as soon as they are paid
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(KittyBattles::prize_pool(0), 50);
+		assert_eq!(Balances::free_balance(KittyBattles::pot_account(0)), 50);
+
+		// Kitty 1 beats kitty 2 while kitty 3 waits, then kitty 3 beats kitty 1
+		System::set_block_number(90);
+		KittyBattles::on_initialize(90);
+		System::assert_last_event(Event::KittyBattles(crate::Event::TournamentWon(
+			0, kitty_3, 3, 50,
+		)));
+		assert_eq!(KittyBattles::tournaments(0), None);
+		assert_eq!(KittyBattles::record(kitty_1), record(1, 1));
+		assert_eq!(KittyBattles::record(kitty_2), record(0, 1));
+		assert_eq!(KittyBattles::record(kitty_3), record(1, 0));
+		assert_eq!(Balances::free_balance(3), 140);
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(KittyBattles::prize_pool(0), 0);
+		assert_eq!(Balances::free_balance(5), 80);
+		assert_eq!(Balances::reserved_balance(5), 0);
+	});
+}
+
+#[test]
+fn tournament_without_opponents_is_cancelled() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::set_balance(Origin::root(), 1, 100, 0);
+		let _ = Balances::set_balance(Origin::root(), 2, 100, 0);
+		let kitty_1 = create_kitty(1, AVERAGE);
+		let kitty_2 = create_kitty(2, STRONG);
+
+		assert_ok!(KittyBattles::create_tournament(Origin::signed(1), 10, 2, 90));
+		assert_ok!(KittyBattles::fund_tournament(Origin::signed(1), 0, 20));
+		assert_ok!(KittyBattles::enter_tournament(Origin::signed(1), 0, kitty_1));
+		assert_ok!(KittyBattles::enter_tournament(Origin::signed(2), 0, kitty_2));
+
+		// Kitty 2 is out once it changes hands, and kitty 1 has no one left to fight
+		assert_ok!(Kitties::transfer(Origin::signed(2), 3, kitty_2));
+		System::set_block_number(90);
+		KittyBattles::on_initialize(90);
+		System::assert_last_event(Event::KittyBattles(crate::Event::TournamentCancelled(0)));
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 100);
+		assert_eq!(KittyBattles::prize_pool(0), 0);
+		assert_eq!(KittyBattles::record(kitty_1), record(0, 0));
+	});
+}
+
+#[test]
+fn kitties_in_use_cannot_battle() {
+	new_test_ext().execute_with(|| {
+		let kitty_1 = create_kitty(1, STRONG);
+		let kitty_2 = create_kitty(2, AVERAGE);
+		let kitty_3 = create_kitty(1, WEAK);
+		let in_auction = create_kitty(1, AVERAGE);
+
+		assert_ok!(Kitties::create_auction(Origin::signed(1), in_auction, 10, 10));
+		assert_noop!(
+			KittyBattles::challenge(Origin::signed(1), in_auction, kitty_2),
+			Error::<Test>::KittyUnavailable
+		);
+		assert_noop!(
+			KittyBattles::challenge(Origin::signed(2), kitty_2, in_auction),
+			Error::<Test>::KittyUnavailable
+		);
+
+		// Kitties put up for auction after the challenge cannot fight it
+		assert_ok!(KittyBattles::challenge(Origin::signed(2), kitty_2, kitty_1));
+		assert_ok!(Kitties::create_auction(Origin::signed(1), kitty_1, 10, 11));
+		assert_noop!(
+			KittyBattles::accept_challenge(Origin::signed(1), kitty_1, kitty_2),
+			Error::<Test>::KittyUnavailable
+		);
+
+		assert_ok!(KittyBattles::challenge(Origin::signed(1), kitty_3, kitty_2));
+		assert_ok!(Kitties::create_auction(Origin::signed(1), kitty_3, 10, 12));
+		assert_noop!(
+			KittyBattles::accept_challenge(Origin::signed(2), kitty_2, kitty_3),
+			Error::<Test>::KittyUnavailable
+		);
+		assert_eq!(KittyBattles::record(kitty_2), record(0, 0));
+	});
+}
+
+#[test]
+fn kitties_in_use_cannot_enter_tournaments() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=3 {
+			let _ = Balances::set_balance(Origin::root(), who, 100, 0);
+		}
+		let in_auction = create_kitty(1, AVERAGE);
+		let leased = create_kitty(1, AVERAGE);
+		let fractionalized = create_kitty(1, AVERAGE);
+		let entered = create_kitty(2, STRONG);
+		let other = create_kitty(3, WEAK);
+
+		assert_ok!(KittyBattles::create_tournament(Origin::signed(3), 10, 4, 90));
+
+		assert_ok!(Kitties::create_auction(Origin::signed(1), in_auction, 10, 10));
+		assert_noop!(
+			KittyBattles::enter_tournament(Origin::signed(1), 0, in_auction),
+			Error::<Test>::KittyUnavailable
+		);
+
+		assert_ok!(Kitties::lease(Origin::signed(1), leased, 2, 10, 10));
+		assert_ok!(Kitties::accept_lease(Origin::signed(2), leased, 10));
+		assert_noop!(
+			KittyBattles::enter_tournament(Origin::signed(1), 0, leased),
+			Error::<Test>::KittyUnavailable
+		);
+
+		assert_ok!(Kitties::fractionalize(Origin::signed(1), fractionalized, 10));
+		assert_noop!(
+			KittyBattles::enter_tournament(Origin::signed(1), 0, fractionalized),
+			Error::<Test>::KittyUnavailable
+		);
+
+		RevealDelay::set(3);
+		assert_ok!(Kitties::create(Origin::signed(1)));
+		let pending = Kitties::kitties_count().unwrap();
+		assert_noop!(
+			KittyBattles::enter_tournament(Origin::signed(1), 0, pending),
+			Error::<Test>::KittyUnavailable
+		);
+
+		// A kitty put up for auction after entering is out, leaving no one to fight
+		assert_ok!(KittyBattles::enter_tournament(Origin::signed(2), 0, entered));
+		assert_ok!(KittyBattles::enter_tournament(Origin::signed(3), 0, other));
+		assert_ok!(Kitties::create_auction(Origin::signed(2), entered, 10, 10));
+		System::set_block_number(90);
+		KittyBattles::on_initialize(90);
+		System::assert_last_event(Event::KittyBattles(crate::Event::TournamentCancelled(0)));
+	});
+}
@@ -15,7 +15,10 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
+	traits::{
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount,
+		NumberFor, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
@@ -32,7 +35,7 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -43,6 +46,8 @@ pub use sp_runtime::{Perbill, Permill};
 
 /// Import the kitties pallet.
 pub use pallet_kitties;
+/// Import the kitty battles pallet.
+pub use pallet_kitty_battles;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -99,7 +104,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -253,6 +258,28 @@ impl pallet_balances::Config for Runtime {
 parameter_types! {
 	pub const TransactionByteFee: Balance = 1;
 	pub const ReserveAmount: Balance = 1_000_000_000;
+	pub const MaxKittiesOwned: u32 = 1_000;
+	pub const AllowInbreeding: bool = false;
+	pub const BreedingCooldown: BlockNumber = 10 * MINUTES;
+	pub const BreedingFee: Balance = 1_000_000_000;
+	pub const MaxAuctionDuration: BlockNumber = 7 * DAYS;
+	pub const MaxAuctionsPerBlock: u32 = 50;
+	pub const MarketplaceEnabled: bool = true;
+	pub const MarketplaceFee: Perbill = Perbill::from_percent(2);
+	pub TreasuryAccount: AccountId = PalletId(*b"py/trsry").into_account();
+	pub const MaxLeaseDuration: BlockNumber = 30 * DAYS;
+	pub const MaxLeasesPerBlock: u32 = 50;
+	pub const KittiesPalletId: PalletId = PalletId(*b"py/kitty");
+	pub const KittyNameDeposit: Balance = 100_000_000;
+	pub const MaxKittyNameLength: u32 = 32;
+	pub const MaxKittyMetadataLength: u32 = 256;
+	pub const KittyRevealDelay: BlockNumber = 3;
+	pub const MaxKittyRevealsPerBlock: u32 = 100;
+	pub const KittyBattlesPalletId: PalletId = PalletId(*b"py/battl");
+	pub const TournamentDeposit: Balance = 1_000_000_000;
+	pub const MaxTournamentEntrants: u32 = 64;
+	pub const MaxTournamentsPerBlock: u32 = 10;
+	pub const MaxTournamentDelay: BlockNumber = 7 * DAYS;
 }
 
 impl pallet_transaction_payment::Config for Runtime {
@@ -275,6 +302,36 @@ impl pallet_kitties::Config for Runtime {
 	type Balance = Balance;
 	type Currency = Balances;
 	type ReserveAmount = ReserveAmount;
+	type AllowInbreeding = AllowInbreeding;
+	type BreedingCooldown = BreedingCooldown;
+	type BreedingFee = BreedingFee;
+	type MaxAuctionDuration = MaxAuctionDuration;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MarketplaceEnabled = MarketplaceEnabled;
+	type MarketplaceFee = MarketplaceFee;
+	type TreasuryAccount = TreasuryAccount;
+	type MaxKittiesOwned = MaxKittiesOwned;
+	type MaxLeaseDuration = MaxLeaseDuration;
+	type MaxLeasesPerBlock = MaxLeasesPerBlock;
+	type PalletId = KittiesPalletId;
+	type NameDeposit = KittyNameDeposit;
+	type MaxNameLength = MaxKittyNameLength;
+	type MaxMetadataLength = MaxKittyMetadataLength;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type RevealDelay = KittyRevealDelay;
+	type MaxRevealsPerBlock = MaxKittyRevealsPerBlock;
+	type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
+}
+
+/// Configure the pallet-kitty-battles in pallets/kitty-battles.
+impl pallet_kitty_battles::Config for Runtime {
+	type Event = Event;
+	type PalletId = KittyBattlesPalletId;
+	type TournamentDeposit = TournamentDeposit;
+	type MaxEntrants = MaxTournamentEntrants;
+	type MaxTournamentsPerBlock = MaxTournamentsPerBlock;
+	type MaxTournamentDelay = MaxTournamentDelay;
+	type WeightInfo = pallet_kitty_battles::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -293,7 +350,8 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the pallet-kitties in the runtime.
-		Kitties: pallet_kitties::{Pallet, Call, Storage, Event<T>},
+		Kitties: pallet_kitties::{Pallet, Call, Storage, Event<T>, Config<T>},
+		KittyBattles: pallet_kitty_battles::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -322,6 +380,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
+	pallet_kitties::migrations::v1::MigrateFromV0<Runtime>,
 >;
 
 impl_runtime_apis! {
@@ -451,6 +510,31 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_runtime_api::KittiesApi<Block, AccountId, u32, BlockNumber> for Runtime {
+		fn kitties_of(owner: AccountId) -> Vec<u32> {
+			Kitties::kitties_of(&owner)
+		}
+
+		fn family_tree(
+			kitty_id: u32,
+			depth: u32,
+		) -> Option<pallet_kitties_runtime_api::FamilyTree<u32, BlockNumber>> {
+			Kitties::family_tree(kitty_id, depth)
+		}
+
+		fn kitty_traits(kitty_id: u32) -> Option<pallet_kitties_runtime_api::KittyTraits> {
+			Kitties::kitty_traits(kitty_id)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString> {
+			let weight = Executive::try_runtime_upgrade()?;
+			Ok((weight, BlockWeights::get().max_block))
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -487,6 +571,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_kitties, Kitties);
+			add_benchmark!(params, batches, pallet_kitty_battles, KittyBattles);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok((batches, storage_info))
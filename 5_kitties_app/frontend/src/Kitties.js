@@ -1,5 +1,6 @@
 import React, { useEffect, useState } from "react";
 import { Form, Grid } from "semantic-ui-react";
+import { u8aToHex } from "@polkadot/util";
 
 import { useSubstrate } from "./substrate-lib";
 import { TxButton } from "./substrate-lib/components";
@@ -31,33 +32,57 @@ export default function Kitties(props) {
     });
   };
 
+  // The traits are decoded by the runtime, through `KittiesApi::kitty_traits`
+  const fetchTraits = async (id) => {
+    const encodedId = u8aToHex(api.createType("KittyIndex", id).toU8a());
+    const result = await api.rpc.state.call("KittiesApi_kitty_traits", encodedId);
+    const traits = api.createType("Option<KittyTraits>", result);
+
+    if (traits.isNone) {
+      return null
+    }
+    const { body_color, eye_shape, accessory, pattern, mouth, gender, rarity_score } = traits.unwrap();
+    return {
+      bodyColor: body_color.toNumber(),
+      eyeShape: eye_shape.toNumber(),
+      accessory: accessory.toNumber(),
+      pattern: pattern.toNumber(),
+      mouth: mouth.toNumber(),
+      gender: gender.toString(),
+      rarityScore: rarity_score.toNumber(),
+    }
+  };
+
   const populateKitties = () => {
     console.log("api changed pop");
     console.log("count is %d", kittiesCount)
 
+    // `kittiesCount` holds the last index, kitties are numbered from 1
     const kittiesRaw = [];
-    for (let i = 0; i < kittiesCount; i++) {
+    for (let i = 1; i <= kittiesCount; i++) {
       api.queryMulti(
         [
           [api.query.kittiesModule.kitties, i],
           [api.query.kittiesModule.owner, i],
+          [api.query.kittiesModule.kittyNames, i],
         ],
-        ([kittyRaw, owner]) => {
-          const kitty = {
-            id: i,
-            dna: kittyRaw.unwrapOr(null).toU8a(),
-            owner: owner.unwrapOr(null).toHuman(),
-          }
+        ([kittyRaw, owner, kittyName]) => {
+          fetchTraits(i).then((traits) => {
+            const kitty = {
+              id: i,
+              dna: kittyRaw.isSome ? kittyRaw.unwrap().dna.toU8a() : null,
+              owner: owner.isSome ? owner.unwrap().toHuman() : null,
+              name: kittyName.isSome ? kittyName.unwrap().name.toUtf8() : null,
+              traits,
+            }
 
-          console.log(kitty);
-          if (i >= kittiesRaw.length) {
-            kittiesRaw.push(kitty)
-          } else {
-            kittiesRaw[i] = kitty
-          }
-          if (kittiesCount === kittiesRaw.length) {
-            setKitties(kittiesRaw)
-          }
+            console.log(kitty);
+            // the traits resolve out of order, so place every kitty by its index
+            kittiesRaw[i - 1] = kitty
+            if (kittiesCount === kittiesRaw.filter(Boolean).length) {
+              setKitties([...kittiesRaw])
+            }
+          });
         }
       );
     }
@@ -83,26 +83,24 @@ const IMAGES = {
   ]
 }
 
-const dnaToAttributes = dna => {
-  const attribute = (index, type) => IMAGES[type][dna[index] % IMAGES[type].length]
-
-  return {
-    body: attribute(0, 'body'),
-    eyes: attribute(1, 'eyes'),
-    accessory: attribute(2, 'accessory'),
-    fur: attribute(3, 'fur'),
-    mouth: attribute(4, 'mouth')
-  }
-}
+// The traits are decoded from the DNA by the runtime, `KittyTraits` has as many variants of
+// each part as there are images here
+const traitsToAttributes = traits => ({
+  body: IMAGES.body[traits.bodyColor],
+  eyes: IMAGES.eyes[traits.eyeShape],
+  accessory: IMAGES.accessory[traits.accessory],
+  fur: IMAGES.fur[traits.pattern],
+  mouth: IMAGES.mouth[traits.mouth]
+})
 
 const KittyAvatar = props => {
   const outerStyle = { height: '160px', position: 'relative', width: '50%' }
   const innerStyle = { height: '150px', position: 'absolute', top: '3%', left: '50%' }
-  const { dna } = props
+  const { traits } = props
 
-  if (!dna) return null
+  if (!traits) return null
 
-  const cat = dnaToAttributes(dna)
+  const cat = traitsToAttributes(traits)
   return <div style={outerStyle}>
     <img alt='body' src={cat.body} style={innerStyle} />
     <img alt='fur' src={cat.fur} style={innerStyle} />
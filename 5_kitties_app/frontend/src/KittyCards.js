@@ -47,20 +47,24 @@ const TransferModal = props => {
 
 const KittyCard = props => {
   const { kitty, accountPair, setStatus } = props
-  const { id = null, dna = null, owner = null } = kitty
+  const { id = null, dna = null, owner = null, name = null, traits = null } = kitty
   const displayDna = dna && dna.join(', ')
   const displayId = id === null ? '' : (id < 10 ? `0${id}` : id.toString())
   const isSelf = accountPair.address === kitty.owner
 
   return <Card>
     { isSelf && <Label as='a' floating color='teal'>我的</Label> }
-    <KittyAvatar dna={dna} />
+    <KittyAvatar traits={traits} />
     <Card.Content>
-      <Card.Header>ID 号: {displayId}</Card.Header>
+      <Card.Header>{name || `ID 号: ${displayId}`}</Card.Header>
+      { name && <Card.Meta>ID 号: {displayId}</Card.Meta> }
       <Card.Meta style={{ overflowWrap: 'break-word' }}>
         基因: <br/>
         {displayDna}
       </Card.Meta>
+      { traits && <Card.Meta>
+        性别: {traits.gender === 'Female' ? '母' : '公'} &nbsp; 稀有度: {traits.rarityScore}
+      </Card.Meta> }
       <Card.Description>
         <p style={{ overflowWrap: 'break-word' }}>
           猫奴:<br/>
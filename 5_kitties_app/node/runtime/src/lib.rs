@@ -13,7 +13,8 @@ use sp_runtime::{
 	transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
-	BlakeTwo256, Block as BlockT, AccountIdLookup, Verify, IdentifyAccount, NumberFor,
+	BlakeTwo256, Block as BlockT, AccountIdLookup, AccountIdConversion, Verify, IdentifyAccount,
+	NumberFor,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -30,7 +31,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_balances::Call as BalancesCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
-	construct_runtime, parameter_types, StorageValue,
+	construct_runtime, parameter_types, PalletId, StorageValue,
 	traits::{KeyOwnerProofSystem, Randomness},
 	weights::{
 		Weight, IdentityFee,
@@ -98,7 +99,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -273,9 +274,57 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const KittyReserveAmount: Balance = 0;
+	pub const MaxKittiesOwned: u32 = 1_000;
+	pub const AllowInbreeding: bool = true;
+	pub const BreedingCooldown: BlockNumber = 0;
+	pub const BreedingFee: Balance = 0;
+	pub const MaxAuctionDuration: BlockNumber = 7 * DAYS;
+	pub const MaxAuctionsPerBlock: u32 = 50;
+	pub const MarketplaceEnabled: bool = false;
+	pub const MarketplaceFee: Perbill = Perbill::from_percent(2);
+	pub TreasuryAccount: AccountId = PalletId(*b"py/trsry").into_account();
+	pub const MaxLeaseDuration: BlockNumber = 30 * DAYS;
+	pub const MaxLeasesPerBlock: u32 = 50;
+	pub const KittiesPalletId: PalletId = PalletId(*b"py/kitty");
+	pub const KittyNameDeposit: Balance = 100_000_000;
+	pub const MaxKittyNameLength: u32 = 32;
+	pub const MaxKittyMetadataLength: u32 = 256;
+	/// Kitties are revealed at once on this development chain.
+	pub const KittyRevealDelay: BlockNumber = 0;
+	pub const MaxKittyRevealsPerBlock: u32 = 100;
+	/// The pallet keeps its name, its storage is migrated in place.
+	pub const KittiesAppPalletName: &'static str = "KittiesModule";
+}
+
+/// Configure the pallet-kitties shared with 4_kitties, without deposits nor marketplace.
 impl pallet_kitties::Config for Runtime {
 	type Event = Event;
 	type Randomness = RandomnessCollectiveFlip;
+	type KittyIndex = u32;
+	type Balance = Balance;
+	type Currency = Balances;
+	type ReserveAmount = KittyReserveAmount;
+	type AllowInbreeding = AllowInbreeding;
+	type BreedingCooldown = BreedingCooldown;
+	type BreedingFee = BreedingFee;
+	type MaxAuctionDuration = MaxAuctionDuration;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MarketplaceEnabled = MarketplaceEnabled;
+	type MarketplaceFee = MarketplaceFee;
+	type TreasuryAccount = TreasuryAccount;
+	type MaxKittiesOwned = MaxKittiesOwned;
+	type MaxLeaseDuration = MaxLeaseDuration;
+	type MaxLeasesPerBlock = MaxLeasesPerBlock;
+	type PalletId = KittiesPalletId;
+	type NameDeposit = KittyNameDeposit;
+	type MaxNameLength = MaxKittyNameLength;
+	type MaxMetadataLength = MaxKittyMetadataLength;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type RevealDelay = KittyRevealDelay;
+	type MaxRevealsPerBlock = MaxKittyRevealsPerBlock;
+	type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -325,6 +374,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
+	pallet_kitties::migrations::v1::MigrateFromKittiesApp<Runtime, KittiesAppPalletName>,
 >;
 
 impl_runtime_apis! {
@@ -453,6 +503,31 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_runtime_api::KittiesApi<Block, AccountId, u32, BlockNumber> for Runtime {
+		fn kitties_of(owner: AccountId) -> Vec<u32> {
+			KittiesModule::kitties_of(&owner)
+		}
+
+		fn family_tree(
+			kitty_id: u32,
+			depth: u32,
+		) -> Option<pallet_kitties_runtime_api::FamilyTree<u32, BlockNumber>> {
+			KittiesModule::family_tree(kitty_id, depth)
+		}
+
+		fn kitty_traits(kitty_id: u32) -> Option<pallet_kitties_runtime_api::KittyTraits> {
+			KittiesModule::kitty_traits(kitty_id)
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	impl frame_try_runtime::TryRuntime<Block> for Runtime {
+		fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString> {
+			let weight = Executive::try_runtime_upgrade()?;
+			Ok((weight, BlockWeights::get().max_block))
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(